@@ -10,6 +10,20 @@ pub struct Bounds {
     pub max: Vector<f64>,
 }
 
+/// A transform from one coordinate reference system to another, e.g. a reprojection.
+///
+/// Implemented for closures, so `|v: Vector<f64>| v` is the identity transform.
+pub trait CoordinateTransform {
+    /// Transforms a coordinate.
+    fn transform(&self, coordinate: Vector<f64>) -> Vector<f64>;
+}
+
+impl<F: Fn(Vector<f64>) -> Vector<f64>> CoordinateTransform for F {
+    fn transform(&self, coordinate: Vector<f64>) -> Vector<f64> {
+        self(coordinate)
+    }
+}
+
 impl Bounds {
     /// Grows the bounds to encompass this point in xyz space.
     ///
@@ -42,6 +56,139 @@ impl Bounds {
             self.max.z = point.z;
         }
     }
+
+    /// Returns true if this point is inside these bounds, including on their edges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Bounds, Point, Vector};
+    /// let bounds = Bounds { min: Vector { x: 0., y: 0., z: 0. }, max: Vector { x: 1., y: 1., z: 1. } };
+    /// assert!(bounds.contains(&Point { x: 1., y: 0.5, z: 0., ..Default::default() }));
+    /// assert!(!bounds.contains(&Point { x: 2., ..Default::default() }));
+    /// ```
+    pub fn contains(&self, point: &Point) -> bool {
+        point.x >= self.min.x
+            && point.x <= self.max.x
+            && point.y >= self.min.y
+            && point.y <= self.max.y
+            && point.z >= self.min.z
+            && point.z <= self.max.z
+    }
+
+    /// Computes the bounds of these points.
+    ///
+    /// Unlike `Bounds::default`, which is inverted so that it can be grown, the bounds of no
+    /// points are all zeros. Use this to check a header's bounds against its points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Bounds, Point, Vector};
+    /// let points = vec![
+    ///     Point { x: 1., y: 2., z: 3., ..Default::default() },
+    ///     Point { x: -1., y: 4., z: 3., ..Default::default() },
+    /// ];
+    /// let bounds = Bounds::compute(&points);
+    /// assert_eq!(Vector { x: -1., y: 2., z: 3. }, bounds.min);
+    /// assert_eq!(Vector { x: 1., y: 4., z: 3. }, bounds.max);
+    /// assert_eq!(Vector::default(), Bounds::compute(&[]).min);
+    /// ```
+    pub fn compute(points: &[Point]) -> Bounds {
+        if points.is_empty() {
+            return Bounds {
+                min: Vector::default(),
+                max: Vector::default(),
+            };
+        }
+        let mut bounds = Bounds::default();
+        for point in points {
+            bounds.grow(point);
+        }
+        bounds
+    }
+
+    /// Returns the center of these bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Bounds, Vector};
+    /// let bounds = Bounds {
+    ///     min: Vector { x: 0., y: 0., z: 0. },
+    ///     max: Vector { x: 2., y: 4., z: 6. },
+    /// };
+    /// assert_eq!(Vector { x: 1., y: 2., z: 3. }, bounds.center());
+    /// ```
+    pub fn center(&self) -> Vector<f64> {
+        Vector {
+            x: (self.min.x + self.max.x) / 2.,
+            y: (self.min.y + self.max.y) / 2.,
+            z: (self.min.z + self.max.z) / 2.,
+        }
+    }
+
+    /// Returns the axis-aligned box that encloses these bounds after a coordinate transform.
+    ///
+    /// Only the eight corners are transformed, so for transforms that bend straight lines the
+    /// result may not enclose every point inside these bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Bounds, Vector};
+    /// let bounds = Bounds {
+    ///     min: Vector { x: 0., y: 0., z: 0. },
+    ///     max: Vector { x: 2., y: 4., z: 6. },
+    /// };
+    /// let swapped = bounds.transform(&|v: Vector<f64>| Vector { x: v.y, y: v.x, z: v.z });
+    /// assert_eq!(Vector { x: 4., y: 2., z: 6. }, swapped.max);
+    /// ```
+    pub fn transform<T: CoordinateTransform>(&self, t: &T) -> Bounds {
+        let mut bounds = Bounds::default();
+        for &x in &[self.min.x, self.max.x] {
+            for &y in &[self.min.y, self.max.y] {
+                for &z in &[self.min.z, self.max.z] {
+                    let corner = t.transform(Vector { x, y, z });
+                    bounds.grow(&Point {
+                        x: corner.x,
+                        y: corner.y,
+                        z: corner.z,
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+        bounds
+    }
+
+    /// Shifts these points so that the center of their bounds is at the origin.
+    ///
+    /// This changes the points' absolute coordinates. The applied shift is returned, so the
+    /// original coordinates can be recovered by adding it back. If there are no points, nothing
+    /// is changed and a zero shift is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Bounds, Point, Vector};
+    /// let mut points = vec![
+    ///     Point { x: 10., y: 20., z: 30., ..Default::default() },
+    ///     Point { x: 12., y: 24., z: 36., ..Default::default() },
+    /// ];
+    /// let center = Bounds::recenter(&mut points);
+    /// assert_eq!(Vector { x: 11., y: 22., z: 33. }, center);
+    /// assert_eq!(-1., points[0].x);
+    /// ```
+    pub fn recenter(points: &mut [Point]) -> Vector<f64> {
+        let center = Bounds::compute(points).center();
+        for point in points.iter_mut() {
+            point.x -= center.x;
+            point.y -= center.y;
+            point.z -= center.z;
+        }
+        center
+    }
 }
 
 impl Default for Bounds {
@@ -109,4 +256,100 @@ mod tests {
         assert_eq!(2., bounds.min.z);
         assert_eq!(4., bounds.max.z);
     }
+
+    #[test]
+    fn recenter() {
+        let mut points = vec![
+            Point {
+                x: 1000.,
+                y: 2000.,
+                z: 10.,
+                ..Default::default()
+            },
+            Point {
+                x: 1010.,
+                y: 2030.,
+                z: 20.,
+                ..Default::default()
+            },
+            Point {
+                x: 1004.,
+                y: 2001.,
+                z: 12.,
+                ..Default::default()
+            },
+        ];
+        let center = Bounds::recenter(&mut points);
+        assert_eq!(1005., center.x);
+        assert_eq!(2015., center.y);
+        assert_eq!(15., center.z);
+
+        let mut bounds = Bounds::default();
+        for point in &points {
+            bounds.grow(point);
+        }
+        let new_center = bounds.center();
+        assert!(new_center.x.abs() < 1e-9);
+        assert!(new_center.y.abs() < 1e-9);
+        assert!(new_center.z.abs() < 1e-9);
+    }
+
+    #[test]
+    fn transform_identity() {
+        let bounds = Bounds {
+            min: Vector {
+                x: -1.,
+                y: 2.,
+                z: 3.,
+            },
+            max: Vector {
+                x: 4.,
+                y: 5.,
+                z: 6.,
+            },
+        };
+        assert_eq!(bounds, bounds.transform(&|v| v));
+    }
+
+    #[test]
+    fn transform_rotation() {
+        let bounds = Bounds {
+            min: Vector {
+                x: 0.,
+                y: 0.,
+                z: 0.,
+            },
+            max: Vector {
+                x: 2.,
+                y: 1.,
+                z: 1.,
+            },
+        };
+        let rotated = bounds.transform(&|v: Vector<f64>| Vector {
+            x: -v.y,
+            y: v.x,
+            z: v.z,
+        });
+        assert_eq!(
+            Vector {
+                x: -1.,
+                y: 0.,
+                z: 0.
+            },
+            rotated.min
+        );
+        assert_eq!(
+            Vector {
+                x: 0.,
+                y: 2.,
+                z: 1.
+            },
+            rotated.max
+        );
+    }
+
+    #[test]
+    fn recenter_empty() {
+        assert_eq!(Vector::default(), Bounds::recenter(&mut []));
+    }
 }
@@ -1,3 +1,5 @@
+use Point;
+
 /// A RGB color value.
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct Color {
@@ -28,4 +30,177 @@ impl Color {
             blue: blue,
         }
     }
+
+    /// Creates a new color from 8-bit channels, scaling them up to the full 16-bit range.
+    ///
+    /// The las spec requires colors to be stored as 16-bit values, so 8-bit channels are
+    /// multiplied by 256.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Color;
+    /// let color = Color::from_8bit(255, 128, 0);
+    /// assert_eq!(Color::new(65280, 32768, 0), color);
+    /// ```
+    pub fn from_8bit(red: u8, green: u8, blue: u8) -> Color {
+        Color::new(
+            u16::from(red) << 8,
+            u16::from(green) << 8,
+            u16::from(blue) << 8,
+        )
+    }
+
+    /// Returns this color's channels scaled down to eight bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Color;
+    /// assert_eq!((255, 128, 0), Color::new(65535, 32768, 0).to_8bit());
+    /// ```
+    pub fn to_8bit(&self) -> (u8, u8, u8) {
+        (
+            (self.red >> 8) as u8,
+            (self.green >> 8) as u8,
+            (self.blue >> 8) as u8,
+        )
+    }
+
+    /// Returns this color's luminance, weighting the channels as 0.299 red, 0.587 green, and 0.114
+    /// blue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Color;
+    /// assert_eq!(65535, Color::new(65535, 65535, 65535).luminance());
+    /// assert_eq!(19595, Color::new(65535, 0, 0).luminance());
+    /// ```
+    pub fn luminance(&self) -> u16 {
+        (0.299 * f64::from(self.red) + 0.587 * f64::from(self.green) + 0.114 * f64::from(self.blue))
+            .round() as u16
+    }
+
+    /// Sets the intensity of each colored point to its color's luminance, e.g. for renderers that
+    /// only show grayscale.
+    ///
+    /// Points without color are left alone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Color, Point};
+    /// let mut points = vec![Point { color: Some(Color::new(0, 65535, 0)), ..Default::default() }];
+    /// Color::luminance_to_intensity(&mut points);
+    /// assert_eq!(38469, points[0].intensity);
+    /// ```
+    pub fn luminance_to_intensity(points: &mut [Point]) {
+        for point in points {
+            if let Some(luminance) = point.luminance() {
+                point.intensity = luminance;
+            }
+        }
+    }
+
+    /// Scales the colors of these points up to the full 16-bit range if they look like 8-bit
+    /// colors.
+    ///
+    /// Colors are considered to be 8-bit if no channel of any point exceeds 255. Returns true if
+    /// the colors were scaled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Color, Point};
+    /// let mut points = vec![Point { color: Some(Color::new(255, 0, 1)), ..Default::default() }];
+    /// assert!(Color::normalize_to_16bit(&mut points));
+    /// assert_eq!(Some(Color::new(65280, 0, 256)), points[0].color);
+    /// assert!(!Color::normalize_to_16bit(&mut points));
+    /// ```
+    pub fn normalize_to_16bit(points: &mut [Point]) -> bool {
+        let max = points
+            .iter()
+            .filter_map(|point| point.color)
+            .map(|color| color.red.max(color.green).max(color.blue))
+            .max();
+        match max {
+            Some(max) if max <= 255 => {
+                for color in points.iter_mut().filter_map(|point| point.color.as_mut()) {
+                    *color = Color::from_8bit(color.red as u8, color.green as u8, color.blue as u8);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn luminance() {
+        let color = Color::new(1000, 2000, 3000);
+        assert_eq!(1815, color.luminance());
+        let point = Point {
+            color: Some(color),
+            ..Default::default()
+        };
+        assert_eq!(Some(1815), point.luminance());
+        assert_eq!(None, Point::default().luminance());
+    }
+
+    #[test]
+    fn luminance_to_intensity() {
+        let mut points = vec![
+            Point {
+                color: Some(Color::new(1000, 2000, 3000)),
+                ..Default::default()
+            },
+            Point {
+                intensity: 42,
+                ..Default::default()
+            },
+        ];
+        Color::luminance_to_intensity(&mut points);
+        assert_eq!(1815, points[0].intensity);
+        assert_eq!(42, points[1].intensity);
+    }
+
+    #[test]
+    fn normalize_to_16bit() {
+        let mut points = vec![
+            Point {
+                color: Some(Color::new(255, 255, 255)),
+                ..Default::default()
+            },
+            Point::default(),
+            Point {
+                color: Some(Color::new(0, 1, 2)),
+                ..Default::default()
+            },
+        ];
+        assert!(Color::normalize_to_16bit(&mut points));
+        assert_eq!(Some(Color::new(65280, 65280, 65280)), points[0].color);
+        assert_eq!(None, points[1].color);
+        assert_eq!(Some(Color::new(0, 256, 512)), points[2].color);
+    }
+
+    #[test]
+    fn normalize_to_16bit_already_16bit() {
+        let mut points = vec![Point {
+            color: Some(Color::new(256, 0, 0)),
+            ..Default::default()
+        }];
+        assert!(!Color::normalize_to_16bit(&mut points));
+        assert_eq!(Some(Color::new(256, 0, 0)), points[0].color);
+    }
+
+    #[test]
+    fn normalize_to_16bit_no_colors() {
+        let mut points = vec![Point::default()];
+        assert!(!Color::normalize_to_16bit(&mut points));
+    }
 }
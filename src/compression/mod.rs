@@ -1,10 +1,10 @@
 use error::Error;
 use laz::las::laszip::{LazVlr, LASZIP_DESCRIPTION, LASZIP_RECORD_ID, LASZIP_USER_ID};
-use reader::{read_point_from, PointReader};
+use reader::{decode_point, decode_point_fields, Error as ReaderError, FieldMask, PointReader};
 use std::fmt::Debug;
 /// Module with functions and structs specific to brigde the las crate and laz crate to allow
 /// writing & reading LAZ data
-use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+use std::io::{Cursor, ErrorKind, Read, Seek, SeekFrom, Write};
 use writer::{write_header_and_vlrs_to, write_point_to, PointWriter};
 use {Header, Point, Result, Vlr};
 
@@ -40,6 +40,7 @@ pub(crate) struct CompressedPointReader<'a, R: Read + Seek> {
     /// in-memory buffer where the decompressor writes decompression result
     decompressor_output: Cursor<Vec<u8>>,
     last_point_idx: u64,
+    fields: FieldMask,
 }
 
 impl<'a, R: Read + Seek> CompressedPointReader<'a, R> {
@@ -55,6 +56,7 @@ impl<'a, R: Read + Seek> CompressedPointReader<'a, R> {
             header,
             decompressor_output,
             last_point_idx: 0,
+            fields: FieldMask::all(),
         })
     }
 }
@@ -73,19 +75,48 @@ impl<'a, R: Read + Seek> PointReader for CompressedPointReader<'a, R> {
     fn read_next(&mut self) -> Option<Result<Point>> {
         if self.last_point_idx < self.header.number_of_points() {
             self.last_point_idx += 1;
-            self.decompressor
+            match self
+                .decompressor
                 .decompress_one(&mut self.decompressor_output.get_mut())
-                .unwrap();
-            if let Err(e) = self.decompressor_output.seek(SeekFrom::Start(0)) {
-                Some(Err(e.into()))
-            } else {
-                Some(read_point_from(&mut self.decompressor_output, &self.header))
+            {
+                Ok(()) => Some(decode_point_fields(
+                    self.decompressor_output.get_ref(),
+                    &self.header,
+                    self.fields,
+                )),
+                Err(ref err) if err.kind() == ErrorKind::UnexpectedEof => {
+                    Some(Err(ReaderError::PointCountMismatch {
+                        declared: self.header.number_of_points(),
+                        actual: self.last_point_idx - 1,
+                    }
+                    .into()))
+                }
+                Err(err) => Some(Err(err.into())),
             }
         } else {
             None
         }
     }
 
+    fn read_next_raw(&mut self) -> Option<Result<&[u8]>> {
+        if self.last_point_idx < self.header.number_of_points() {
+            self.last_point_idx += 1;
+            match self
+                .decompressor
+                .decompress_one(&mut self.decompressor_output.get_mut())
+            {
+                Ok(()) => Some(Ok(&self.decompressor_output.get_ref()[..])),
+                Err(err) => Some(Err(err.into())),
+            }
+        } else {
+            None
+        }
+    }
+
+    fn set_fields(&mut self, fields: FieldMask) {
+        self.fields = fields;
+    }
+
     fn seek(&mut self, position: u64) -> Result<()> {
         self.last_point_idx = position;
         self.decompressor.seek(position)?;
@@ -95,6 +126,14 @@ impl<'a, R: Read + Seek> PointReader for CompressedPointReader<'a, R> {
     fn header(&self) -> &Header {
         &self.header
     }
+
+    fn points_read(&self) -> u64 {
+        self.last_point_idx
+    }
+
+    fn offset_to_point_data(&self) -> Option<u64> {
+        None
+    }
 }
 
 /// struct that knows how to write LAZ
@@ -153,11 +192,18 @@ impl<'a, W: Write + Seek> CompressedPointWriter<'a, W> {
 
 impl<'a, W: Write + Seek> PointWriter<W> for CompressedPointWriter<'a, W> {
     fn write_next(&mut self, point: Point) -> Result<()> {
-        self.header.add_point(&point);
         self.compressor_input.seek(SeekFrom::Start(0))?;
-        write_point_to(&mut self.compressor_input, point, &self.header)?;
+        write_point_to(&mut self.compressor_input, &point, &self.header)?;
         self.compressor
             .compress_one(self.compressor_input.get_ref())?;
+        self.header.add_point(&point);
+        Ok(())
+    }
+
+    fn write_next_raw(&mut self, bytes: &[u8]) -> Result<()> {
+        let point = decode_point(bytes, &self.header)?;
+        self.compressor.compress_one(bytes)?;
+        self.header.add_point(&point);
         Ok(())
     }
 
@@ -0,0 +1,202 @@
+use std::fmt;
+use {Header, Point};
+
+/// The differences between two sets of las data, each a header and its points.
+///
+/// This gives more detail than comparing with `PartialEq`, e.g. when checking output against a
+/// known-good file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Diff {
+    /// The names of the header fields that differ, not including the vlrs.
+    pub header_fields: Vec<&'static str>,
+
+    /// The number of vlrs and evlrs in each header, if the counts differ.
+    pub vlr_counts: Option<(usize, usize)>,
+
+    /// The indices, in `Header::all_vlrs` order, of the vlrs that are in both headers but differ.
+    pub vlrs: Vec<usize>,
+
+    /// The number of points in each set, if the counts differ.
+    pub point_counts: Option<(usize, usize)>,
+
+    /// The index of the first point that differs, among the points that are in both sets.
+    pub first_point: Option<usize>,
+
+    /// How many points differ, among the points that are in both sets.
+    pub points: usize,
+
+    /// How many points have different x, y, or z coordinates.
+    pub coordinates: usize,
+}
+
+impl Diff {
+    /// Compares a header and its points to another header and its points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Diff, Header, Point};
+    /// let header = Header::default();
+    /// let points = vec![Point::default(); 3];
+    /// let mut other = points.clone();
+    /// other[1].intensity = 42;
+    /// let diff = Diff::new(&header, &points, &header, &other);
+    /// assert_eq!(Some(1), diff.first_point);
+    /// assert_eq!(0, diff.coordinates);
+    /// ```
+    pub fn new(
+        header: &Header,
+        points: &[Point],
+        other_header: &Header,
+        other_points: &[Point],
+    ) -> Diff {
+        let mut diff = Diff::default();
+
+        macro_rules! compare {
+            ($($field:ident),*) => {
+                $(
+                    if header.$field() != other_header.$field() {
+                        diff.header_fields.push(stringify!($field));
+                    }
+                )*
+            };
+        }
+        compare!(
+            file_source_id,
+            gps_time_type,
+            has_synthetic_return_numbers,
+            guid,
+            version,
+            system_identifier,
+            generating_software,
+            date,
+            padding,
+            point_format,
+            transforms,
+            bounds,
+            number_of_points
+        );
+        if (1..16).any(|n| {
+            header.number_of_points_by_return(n) != other_header.number_of_points_by_return(n)
+        }) {
+            diff.header_fields.push("number_of_points_by_return");
+        }
+        compare!(
+            vlr_padding,
+            point_padding,
+            start_of_waveform_data_packet_record
+        );
+
+        let vlr_counts = (header.all_vlrs().count(), other_header.all_vlrs().count());
+        if vlr_counts.0 != vlr_counts.1 {
+            diff.vlr_counts = Some(vlr_counts);
+        }
+        diff.vlrs = header
+            .all_vlrs()
+            .zip(other_header.all_vlrs())
+            .enumerate()
+            .filter(|&(_, (vlr, other))| vlr != other)
+            .map(|(i, _)| i)
+            .collect();
+
+        if points.len() != other_points.len() {
+            diff.point_counts = Some((points.len(), other_points.len()));
+        }
+        for (i, (point, other)) in points.iter().zip(other_points).enumerate() {
+            if point != other {
+                diff.first_point = diff.first_point.or(Some(i));
+                diff.points += 1;
+                if point.x != other.x || point.y != other.y || point.z != other.z {
+                    diff.coordinates += 1;
+                }
+            }
+        }
+        diff
+    }
+
+    /// Returns true if no differences were found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Diff, Header};
+    /// let header = Header::default();
+    /// assert!(Diff::new(&header, &[], &header, &[]).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        *self == Diff::default()
+    }
+}
+
+impl fmt::Display for Diff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "no differences");
+        }
+        let mut parts = Vec::new();
+        if !self.header_fields.is_empty() {
+            parts.push(format!(
+                "header fields differ: {}",
+                self.header_fields.join(", ")
+            ));
+        }
+        if let Some((a, b)) = self.vlr_counts {
+            parts.push(format!("vlr counts differ: {} != {}", a, b));
+        }
+        if !self.vlrs.is_empty() {
+            parts.push(format!("vlrs differ at indices {:?}", self.vlrs));
+        }
+        if let Some((a, b)) = self.point_counts {
+            parts.push(format!("point counts differ: {} != {}", a, b));
+        }
+        if let Some(first_point) = self.first_point {
+            parts.push(format!(
+                "{} points differ ({} in coordinates), starting at index {}",
+                self.points, self.coordinates, first_point
+            ));
+        }
+        write!(f, "{}", parts.join("; "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {Builder, Vlr};
+
+    #[test]
+    fn identical() {
+        let header = Header::default();
+        let points = vec![Point::default(); 2];
+        let diff = Diff::new(&header, &points, &header, &points);
+        assert!(diff.is_empty());
+        assert_eq!("no differences", diff.to_string());
+    }
+
+    #[test]
+    fn header_fields() {
+        let mut builder = Builder::default();
+        let header = builder.clone().into_header().unwrap();
+        builder.system_identifier = "other".to_string();
+        builder.vlrs.push(Vlr::default());
+        let other = builder.into_header().unwrap();
+        let diff = Diff::new(&header, &[], &other, &[]);
+        assert_eq!(vec!["system_identifier"], diff.header_fields);
+        assert_eq!(Some((0, 1)), diff.vlr_counts);
+        assert!(diff.vlrs.is_empty());
+    }
+
+    #[test]
+    fn points() {
+        let header = Header::default();
+        let points = vec![Point::default(); 3];
+        let mut other = points.clone();
+        other[2].x = 1.;
+        other.push(Point::default());
+        let diff = Diff::new(&header, &points, &header, &other);
+        assert_eq!(Some((3, 4)), diff.point_counts);
+        assert_eq!(Some(2), diff.first_point);
+        assert_eq!(1, diff.points);
+        assert_eq!(1, diff.coordinates);
+    }
+}
@@ -1,3 +1,6 @@
+const SECONDS_PER_WEEK: f64 = 604_800.;
+const STANDARD_TIME_OFFSET: f64 = 1e9;
+
 /// The meaning of GPS time in the point records.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum GpsTimeType {
@@ -23,6 +26,32 @@ impl GpsTimeType {
             GpsTimeType::Standard => true,
         }
     }
+
+    /// Converts a gps time value from this time type to another.
+    ///
+    /// Converting week time to standard time needs the gps week number that the week time is
+    /// relative to. Converting standard time to week time drops the week, so `week_number` is
+    /// ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::GpsTimeType;
+    /// let standard = GpsTimeType::Week.convert(3600., GpsTimeType::Standard, 1654);
+    /// assert_eq!(1654. * 604800. + 3600. - 1e9, standard);
+    /// assert_eq!(3600., GpsTimeType::Standard.convert(standard, GpsTimeType::Week, 0));
+    /// ```
+    pub fn convert(&self, gps_time: f64, target: GpsTimeType, week_number: u32) -> f64 {
+        match (*self, target) {
+            (GpsTimeType::Week, GpsTimeType::Standard) => {
+                f64::from(week_number) * SECONDS_PER_WEEK + gps_time - STANDARD_TIME_OFFSET
+            }
+            (GpsTimeType::Standard, GpsTimeType::Week) => {
+                (gps_time + STANDARD_TIME_OFFSET).rem_euclid(SECONDS_PER_WEEK)
+            }
+            _ => gps_time,
+        }
+    }
 }
 
 impl From<GpsTimeType> for u16 {
@@ -37,6 +37,11 @@ pub struct Builder {
     /// Discouraged.
     pub point_padding: Vec<u8>,
 
+    /// The offset to the first waveform data packet record, if waveform data is stored in the file.
+    ///
+    /// Only las 1.3 and later support this.
+    pub start_of_waveform_data_packet_record: Option<u64>,
+
     /// The system that generated the points.
     pub system_identifier: String,
 
@@ -74,23 +79,20 @@ impl Builder {
         use chrono::TimeZone;
         use utils::AsLasStr;
 
-        let number_of_points = if raw_header.number_of_point_records > 0 {
-            u64::from(raw_header.number_of_point_records)
-        } else {
-            raw_header
-                .large_file
-                .map(|l| l.number_of_point_records)
-                .unwrap_or(0)
+        // The 64-bit counts are authoritative when present, since the legacy counts are zeroed
+        // for large files and extended point formats.
+        let number_of_points = match raw_header.large_file {
+            Some(large_file) if large_file.number_of_point_records > 0 => {
+                large_file.number_of_point_records
+            }
+            _ => u64::from(raw_header.number_of_point_records),
+        };
+        let number_of_points_by_return = match raw_header.large_file {
+            Some(large_file) if large_file.number_of_points_by_return.iter().any(|&n| n > 0) => {
+                number_of_points_hash_map(&large_file.number_of_points_by_return)
+            }
+            _ => number_of_points_hash_map(&raw_header.number_of_points_by_return),
         };
-        let number_of_points_by_return =
-            if raw_header.number_of_points_by_return.iter().any(|&n| n > 0) {
-                number_of_points_hash_map(&raw_header.number_of_points_by_return)
-            } else {
-                raw_header
-                    .large_file
-                    .map(|f| number_of_points_hash_map(&f.number_of_points_by_return))
-                    .unwrap_or_else(HashMap::new)
-            };
         let mut point_format = Format::new(raw_header.point_data_record_format)?;
         let n = point_format.len();
         if raw_header.point_data_record_length < n {
@@ -122,6 +124,7 @@ impl Builder {
             has_synthetic_return_numbers: raw_header.global_encoding & 8 == 8,
             padding: raw_header.padding,
             point_format: point_format,
+            start_of_waveform_data_packet_record: raw_header.start_of_waveform_data_packet_record,
             system_identifier: raw_header
                 .system_identifier
                 .as_ref()
@@ -170,7 +173,7 @@ impl Builder {
     /// let header = Builder::new(Default::default()).unwrap().into_header().unwrap();
     /// ```
     pub fn into_header(mut self) -> Result<Header> {
-        use feature::{Evlrs, FileSourceId, GpsStandardTime, SyntheticReturnNumbers};
+        use feature::{Evlrs, FileSourceId, GpsStandardTime, SyntheticReturnNumbers, Waveforms};
         use raw::POINT_DATA_START_SIGNATURE;
 
         let n = self.vlr_padding.len();
@@ -190,6 +193,9 @@ impl Builder {
             self.version.verify_support_for::<GpsStandardTime>()?;
         }
         // TODO check waveforms
+        if self.start_of_waveform_data_packet_record.is_some() {
+            self.version.verify_support_for::<Waveforms>()?;
+        }
         if !self.version.supports_point_format(self.point_format) {
             return Err(Error::Format {
                 version: self.version,
@@ -215,8 +221,6 @@ impl Builder {
         }
         if !evlrs.is_empty() {
             self.version.verify_support_for::<Evlrs>()?;
-        } else if !self.point_padding.is_empty() {
-            return Err(Error::PointPadding.into());
         }
         let header = Header {
             bounds: self.bounds,
@@ -232,6 +236,7 @@ impl Builder {
             padding: self.padding,
             point_format: self.point_format,
             point_padding: self.point_padding,
+            start_of_waveform_data_packet_record: self.start_of_waveform_data_packet_record,
             system_identifier: self.system_identifier,
             transforms: self.transforms,
             version: self.version,
@@ -266,6 +271,7 @@ impl From<Header> for Builder {
             padding: header.padding,
             point_format: header.point_format,
             point_padding: header.point_padding,
+            start_of_waveform_data_packet_record: header.start_of_waveform_data_packet_record,
             system_identifier: header.system_identifier,
             transforms: header.transforms,
             version: header.version,
@@ -341,7 +347,7 @@ mod tests {
     fn point_padding_no_evlrs() {
         let mut builder = Builder::from((1, 4));
         builder.point_padding = vec![0];
-        assert!(builder.into_header().is_err());
+        assert_eq!(vec![0], *builder.into_header().unwrap().point_padding());
     }
 
     #[test]
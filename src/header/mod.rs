@@ -47,7 +47,12 @@
 //! ```
 
 use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufReader, Cursor};
 use std::iter::Chain;
+use std::iter::FromIterator;
+use std::path::Path;
 use std::slice::Iter;
 
 use chrono::{Date, Datelike, Utc};
@@ -55,10 +60,16 @@ use thiserror::Error;
 use uuid::Uuid;
 
 use point::Format;
+use reader::read_header_and_vlrs;
 use utils::FromLasStr;
-use {raw, Bounds, GpsTimeType, Point, Result, Transform, Vector, Version, Vlr};
+use writer::write_header_and_vlrs_to;
+use {
+    raw, Bounds, Color, CoordinateTransform, GpsTimeType, Point, Result, Transform, Vector,
+    Version, Vlr,
+};
 
 pub use self::builder::Builder;
+pub use raw::header::{Severity, ValidationIssue};
 
 mod builder;
 
@@ -74,6 +85,20 @@ pub enum Error {
     #[allow(missing_docs)]
     Format { version: Version, format: Format },
 
+    /// A transform has a scale that isn't positive and finite, or an offset that isn't finite.
+    #[error("the {axis} transform is invalid: {transform}")]
+    #[allow(missing_docs)]
+    InvalidScale { axis: char, transform: Transform },
+
+    /// The extra bytes would make the point data record length overflow.
+    #[error("format {format} cannot hold {len} extra bytes")]
+    #[allow(missing_docs)]
+    ExtraBytes { format: Format, len: u16 },
+
+    /// The point format has no gps time.
+    #[error("the point format has no gps time: {0}")]
+    NoGpsTime(Format),
+
     /// The offset to point data is too large.
     #[error("the offset to the point data is too large: {0}")]
     OffsetToPointDataTooLarge(usize),
@@ -84,6 +109,9 @@ pub enum Error {
     PointDataRecordLength { format: Format, len: u16 },
 
     /// Point padding is only allowed when evlrs are present.
+    ///
+    /// No longer returned, since point padding is also allowed at the end of a file.
+    #[deprecated(note = "point padding is allowed without evlrs, so this is never returned")]
     #[error("point padding is only allowed when evlrs are present")]
     PointPadding,
 
@@ -91,6 +119,19 @@ pub enum Error {
     #[error("the header is too large ({0} bytes) to convert to a raw header")]
     TooLarge(usize),
 
+    /// An extra bytes attribute name is empty, longer than 32 bytes, or already used.
+    #[error("the attribute name is empty, longer than 32 bytes, or already used")]
+    AttributeName,
+
+    /// There isn't one extra bytes attribute value per point.
+    #[error("there are {values} attribute values for {points} points")]
+    #[allow(missing_docs)]
+    AttributeValues { points: usize, values: usize },
+
+    /// The extra bytes schemas of two headers can't be merged.
+    #[error("the extra bytes schemas are incompatible")]
+    IncompatibleExtraBytes,
+
     /// Too many extended variable length records.
     #[error("too many extended variable length records: {0}")]
     TooManyEvlrs(usize),
@@ -132,6 +173,7 @@ pub struct Header {
     padding: Vec<u8>,
     point_format: Format,
     point_padding: Vec<u8>,
+    start_of_waveform_data_packet_record: Option<u64>,
     system_identifier: String,
     transforms: Vector<Transform>,
     version: Version,
@@ -139,12 +181,117 @@ pub struct Header {
     vlrs: Vec<Vlr>,
 }
 
+/// How precisely a header stores a set of coordinates, see `Header::coordinate_precision`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CoordinatePrecision {
+    /// The scale of each axis, i.e. the smallest step that can be stored.
+    pub scale: Vector<f64>,
+
+    /// The largest change to a coordinate on each axis when it's stored.
+    pub max_error: Vector<f64>,
+
+    /// Whether storing the coordinates loses detail.
+    pub is_lossy: bool,
+}
+
+/// An attribute that was dropped from some points by `Header::convert_to_format`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DataLoss {
+    /// The name of the attribute, e.g. "color".
+    pub attribute: &'static str,
+
+    /// The number of points that lost it.
+    pub points: u64,
+}
+
+impl fmt::Display for DataLoss {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} removed from {} points", self.attribute, self.points)
+    }
+}
+
+/// A waveform packet descriptor, see `Header::waveform_descriptors`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WaveformDescriptor {
+    /// The number of bits per waveform sample, e.g. 8 or 16.
+    pub bits_per_sample: u8,
+
+    /// The waveform compression type, zero for uncompressed samples.
+    pub compression_type: u8,
+
+    /// The number of samples in each waveform.
+    pub number_of_samples: u32,
+
+    /// The time between samples, in picoseconds.
+    pub temporal_sample_spacing: u32,
+
+    /// The digitizer gain used to convert samples to volts.
+    pub digitizer_gain: f64,
+
+    /// The digitizer offset used to convert samples to volts.
+    pub digitizer_offset: f64,
+}
+
+/// The position of a tile in the grid made by `Header::split_into_tiles`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TileCoord {
+    /// The tile's index along the x axis.
+    pub column: u64,
+
+    /// The tile's index along the y axis.
+    pub row: u64,
+}
+
 /// An iterator over a header's variable length records.
 ///
 /// Get this iterator via `vlrs` or `evlrs` methods on `Header`.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Vlrs<'a>(Chain<Iter<'a, Vlr>, Iter<'a, Vlr>>);
 
+/// One field of an extra bytes schema, parsed from a 192 byte extra bytes descriptor.
+#[derive(Clone, Debug)]
+struct ExtraBytesField {
+    descriptor: Vec<u8>,
+    len: usize,
+}
+
+impl ExtraBytesField {
+    const DESCRIPTOR_LEN: usize = 192;
+
+    fn new(descriptor: &[u8]) -> Option<ExtraBytesField> {
+        const SIZES: [usize; 10] = [1, 1, 2, 2, 4, 4, 8, 8, 4, 8];
+        let data_type = usize::from(descriptor[2]);
+        let len = match data_type {
+            0 => usize::from(descriptor[3]),
+            1..=30 => SIZES[(data_type - 1) % 10] * ((data_type - 1) / 10 + 1),
+            _ => return None,
+        };
+        Some(ExtraBytesField {
+            descriptor: descriptor.to_vec(),
+            len,
+        })
+    }
+
+    fn name(&self) -> &[u8] {
+        &self.descriptor[4..36]
+    }
+
+    fn name_str(&self) -> &str {
+        let name = self.name();
+        let end = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+        std::str::from_utf8(&name[..end]).unwrap_or("")
+    }
+
+    /// Two fields are the same if they have the same name, type, options, scale, and offset.
+    ///
+    /// The min, max, and description can differ from file to file.
+    fn is_same(&self, other: &ExtraBytesField) -> bool {
+        self.descriptor[2..40] == other.descriptor[2..40]
+            && self.descriptor[40..64] == other.descriptor[40..64]
+            && self.descriptor[112..160] == other.descriptor[112..160]
+    }
+}
+
 impl Header {
     /// Creates a new header from a raw header.
     ///
@@ -159,6 +306,60 @@ impl Header {
         Builder::new(raw_header).and_then(|b| b.into_header())
     }
 
+    /// Reads only the header, vlrs, and evlrs from a las file, without reading any points.
+    ///
+    /// This is fast even for very large files, and works for compressed files even without the
+    /// "laz" feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Header;
+    /// let header = Header::from_path("tests/data/autzen.las").unwrap();
+    /// assert_eq!(106, header.number_of_points());
+    /// ```
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Header> {
+        File::open(path)
+            .map_err(::Error::from)
+            .and_then(|file| read_header_and_vlrs(BufReader::new(file), false))
+    }
+
+    /// Reads a header and its vlrs from bytes, e.g. the start of a las file.
+    ///
+    /// The bytes must hold everything before the point data. Evlrs come after the point data, so a
+    /// header that declares evlrs can't be read this way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Header;
+    /// let bytes = std::fs::read("tests/data/autzen.las").unwrap();
+    /// let header = Header::from_bytes(&bytes).unwrap();
+    /// assert_eq!(106, header.number_of_points());
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Header> {
+        read_header_and_vlrs(Cursor::new(bytes), false)
+    }
+
+    /// Returns the bytes of this header and its vlrs, as they are written before the point data.
+    ///
+    /// Evlrs are written after the point data, so they aren't included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Header;
+    /// let header = Header::default();
+    /// let bytes = header.to_bytes().unwrap();
+    /// assert_eq!(227, bytes.len());
+    /// assert_eq!(header, Header::from_bytes(&bytes).unwrap());
+    /// ```
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        write_header_and_vlrs_to(&mut bytes, self)?;
+        Ok(bytes)
+    }
+
     /// Clears this header's point counts and bounds.
     ///
     /// # Examples
@@ -180,6 +381,69 @@ impl Header {
         self.bounds = Default::default();
     }
 
+    /// Fills in this header's generating software and file creation date, if they're unset.
+    ///
+    /// An empty generating software becomes "las-rs" and this crate's version, and a missing
+    /// date becomes today. Values that are already set are kept. `Writer::new` stamps every
+    /// header it writes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Builder, Header};
+    /// let mut builder = Builder::default();
+    /// builder.generating_software = String::new();
+    /// builder.date = None;
+    /// let mut header = builder.into_header().unwrap();
+    /// header.stamp();
+    /// assert!(header.generating_software().starts_with("las-rs"));
+    /// assert!(header.date().is_some());
+    /// ```
+    pub fn stamp(&mut self) {
+        if self.generating_software.is_empty() || self.date.is_none() {
+            let default = Header::default();
+            if self.generating_software.is_empty() {
+                self.generating_software = default.generating_software;
+            }
+            if self.date.is_none() {
+                self.date = default.date;
+            }
+        }
+    }
+
+    /// Creates a default header that describes these points.
+    ///
+    /// The point format is inferred from the points with `Format::infer`, the version is bumped to
+    /// 1.4 if the default version can't hold that format, and the point counts and bounds are
+    /// computed from the points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Header, Point, Version};
+    /// let points = vec![Point { gps_time: Some(1.), ..Default::default() }; 2];
+    /// let header = Header::from_points(&points);
+    /// assert_eq!(2, header.number_of_points());
+    /// assert!(header.point_format().has_gps_time);
+    ///
+    /// let points = vec![Point { return_number: 8, ..Default::default() }];
+    /// assert_eq!(Version::new(1, 4), Header::from_points(&points).version());
+    /// ```
+    pub fn from_points(points: &[Point]) -> Header {
+        let mut builder = Builder::from(Header::default());
+        builder.point_format = Format::infer(points);
+        if !builder.version.supports_point_format(builder.point_format) {
+            builder.version = Version::new(1, 4);
+        }
+        let mut header = builder
+            .into_header()
+            .expect("las 1.4 supports every point format");
+        for point in points {
+            header.add_point(point);
+        }
+        header
+    }
+
     /// Adds a point to this header, incrementing the point counts and growing the bounds.
     ///
     /// # Examples
@@ -202,6 +466,121 @@ impl Header {
         self.bounds.grow(&point);
     }
 
+    /// Returns the points whose gps time is within `[start, end]`.
+    ///
+    /// Returns an error if this header's point format has no gps time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Builder, Point};
+    /// use las::point::Format;
+    /// let mut builder = Builder::default();
+    /// builder.point_format = Format::new(1).unwrap();
+    /// let header = builder.into_header().unwrap();
+    /// let points: Vec<_> = (0..10)
+    ///     .map(|i| Point { gps_time: Some(f64::from(i)), ..Default::default() })
+    ///     .collect();
+    /// assert_eq!(3, header.filter_by_gps_time(&points, 2., 4.).unwrap().len());
+    /// ```
+    pub fn filter_by_gps_time(&self, points: &[Point], start: f64, end: f64) -> Result<Vec<Point>> {
+        if !self.point_format.has_gps_time {
+            return Err(Error::NoGpsTime(self.point_format).into());
+        }
+        Ok(points
+            .iter()
+            .filter(|point| match point.gps_time {
+                Some(gps_time) => gps_time >= start && gps_time <= end,
+                None => false,
+            })
+            .cloned()
+            .collect())
+    }
+
+    /// Partitions points by their point source id, e.g. to separate merged flight lines.
+    ///
+    /// Each partition gets a copy of this header, including its vlrs, with point counts and bounds
+    /// recomputed from the partition's points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Header, Point};
+    /// let points = vec![
+    ///     Point { point_source_id: 1, ..Default::default() },
+    ///     Point { point_source_id: 2, ..Default::default() },
+    ///     Point { point_source_id: 1, ..Default::default() },
+    /// ];
+    /// let partitions = Header::default().split_by_point_source(&points);
+    /// assert_eq!(2, partitions[&1].1.len());
+    /// assert_eq!(2, partitions[&1].0.number_of_points());
+    /// ```
+    pub fn split_by_point_source(&self, points: &[Point]) -> HashMap<u16, (Header, Vec<Point>)> {
+        let mut partitions = HashMap::new();
+        for point in points {
+            let partition = partitions.entry(point.point_source_id).or_insert_with(|| {
+                let mut header = self.clone();
+                header.clear();
+                (header, Vec::new())
+            });
+            partition.0.add_point(point);
+            partition.1.push(point.clone());
+        }
+        partitions
+    }
+
+    /// Partitions points into a regular grid of square tiles in the xy plane, e.g. for web map
+    /// pyramids.
+    ///
+    /// The grid starts at the points' minimum x and y, and a point goes into the tile at
+    /// `floor((x - min_x) / tile_size)` and `floor((y - min_y) / tile_size)`. Only tiles with points
+    /// are returned. Each tile gets a copy of this header with point counts and bounds recomputed
+    /// from the tile's points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tile_size` is not positive and finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Header, Point};
+    /// use las::header::TileCoord;
+    /// let points = vec![
+    ///     Point { x: 0., y: 0., ..Default::default() },
+    ///     Point { x: 15., y: 0., ..Default::default() },
+    /// ];
+    /// let tiles = Header::default().split_into_tiles(&points, 10.);
+    /// assert_eq!(1, tiles[&TileCoord { column: 1, row: 0 }].1.len());
+    /// ```
+    pub fn split_into_tiles(
+        &self,
+        points: &[Point],
+        tile_size: f64,
+    ) -> HashMap<TileCoord, (Header, Vec<Point>)> {
+        assert!(
+            tile_size > 0. && tile_size.is_finite(),
+            "tile size must be positive and finite: {}",
+            tile_size
+        );
+        let min = Bounds::compute(points).min;
+        let mut tiles = HashMap::new();
+        for point in points {
+            let coord = TileCoord {
+                column: ((point.x - min.x) / tile_size).floor() as u64,
+                row: ((point.y - min.y) / tile_size).floor() as u64,
+            };
+            let tile = tiles.entry(coord).or_insert_with(|| {
+                let mut header = self.clone();
+                header.clear();
+                (header, Vec::new())
+            });
+            tile.0.add_point(point);
+            tile.1.push(point.clone());
+        }
+        tiles
+    }
+
     /// Returns this header's file source id.
     ///
     /// For airborne data, this is often the flight line number.
@@ -258,6 +637,46 @@ impl Header {
         self.guid
     }
 
+    /// Converts these points' gps times to another gps time type, and sets this header's type.
+    ///
+    /// `week_number` is the gps week that week times are relative to, see `GpsTimeType::convert`.
+    /// Returns an error, and changes nothing, if this header's point format has no gps time or if
+    /// this header's version doesn't support the target type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Builder, GpsTimeType, Point};
+    /// use las::point::Format;
+    /// let mut builder = Builder::from((1, 2));
+    /// builder.point_format = Format::new(1).unwrap();
+    /// let mut header = builder.into_header().unwrap();
+    /// let mut points = vec![Point { gps_time: Some(3600.), ..Default::default() }];
+    /// header.convert_gps_time(&mut points, GpsTimeType::Standard, 1654).unwrap();
+    /// assert_eq!(GpsTimeType::Standard, header.gps_time_type());
+    /// assert_eq!(Some(1654. * 604800. + 3600. - 1e9), points[0].gps_time);
+    /// ```
+    pub fn convert_gps_time(
+        &mut self,
+        points: &mut [Point],
+        target: GpsTimeType,
+        week_number: u32,
+    ) -> Result<()> {
+        if !self.point_format.has_gps_time {
+            return Err(Error::NoGpsTime(self.point_format).into());
+        }
+        let mut builder = Builder::from(self.clone());
+        builder.gps_time_type = target;
+        let header = builder.into_header()?;
+        for point in points {
+            if let Some(gps_time) = point.gps_time.as_mut() {
+                *gps_time = self.gps_time_type.convert(*gps_time, target, week_number);
+            }
+        }
+        *self = header;
+        Ok(())
+    }
+
     /// Returns this header's version.
     ///
     /// # Examples
@@ -270,6 +689,28 @@ impl Header {
         self.version
     }
 
+    /// Converts this header to another las version.
+    ///
+    /// The header is validated for the new version, e.g. its point format must be supported, and
+    /// evlrs are moved into the regular vlrs if the new version doesn't support them. The header
+    /// size written to the raw header follows the version. If the header can't be converted, an
+    /// error is returned and this header is unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Header, Version};
+    /// let mut header = Header::from((1, 4));
+    /// header.set_version((1, 2)).unwrap();
+    /// assert_eq!(Version::new(1, 2), header.version());
+    /// ```
+    pub fn set_version<V: Into<Version>>(&mut self, version: V) -> Result<()> {
+        let mut builder = Builder::from(self.clone());
+        builder.version = version.into();
+        *self = builder.into_header()?;
+        Ok(())
+    }
+
     /// Returns this header's system identifier.
     ///
     /// Describes the source of the data, whether it is a sensor or a processing operation.
@@ -339,6 +780,143 @@ impl Header {
         &self.point_format
     }
 
+    /// Sets the number of extra bytes carried by each point record.
+    ///
+    /// The extra bytes are added to the point data record length when writing, and are split
+    /// back out of each record when reading. Every point written with this header must have
+    /// exactly this many extra bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Header;
+    /// let mut header = Header::default();
+    /// header.set_extra_bytes_length(8).unwrap();
+    /// assert_eq!(8, header.point_format().extra_bytes);
+    /// assert_eq!(28, header.point_format().len());
+    /// ```
+    pub fn set_extra_bytes_length(&mut self, len: u16) -> Result<()> {
+        let mut format = self.point_format;
+        format.extra_bytes = 0;
+        if format.len().checked_add(len).is_none() {
+            return Err(Error::ExtraBytes { format, len }.into());
+        }
+        self.point_format.extra_bytes = len;
+        Ok(())
+    }
+
+    /// Converts this header and its points to another point format, reporting what was lost.
+    ///
+    /// Attributes the new format doesn't have are removed from the points, and attributes it adds
+    /// are set to zero (black for color). Extra bytes are truncated or zero-padded to the new
+    /// format's length, and scanner channels are dropped when converting to a non-extended
+    /// format. Each kind of attribute that was removed from at least one point is reported once,
+    /// so a tool can warn before the points are written. Returns an error, and changes nothing, if
+    /// this header's version doesn't support the format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Color, Header, Point};
+    /// use las::point::Format;
+    /// let mut header = Header::from((1, 2));
+    /// let mut points = vec![Point { color: Some(Color::new(1, 2, 3)), ..Default::default() }];
+    /// let losses = header.convert_to_format(&mut points, Format::new(0).unwrap()).unwrap();
+    /// assert_eq!("color removed from 1 points", losses[0].to_string());
+    /// assert_eq!(None, points[0].color);
+    /// ```
+    pub fn convert_to_format(
+        &mut self,
+        points: &mut [Point],
+        format: Format,
+    ) -> Result<Vec<DataLoss>> {
+        let mut builder = Builder::from(self.clone());
+        builder.point_format = format;
+        *self = builder.into_header()?;
+
+        let mut counts = [0u64; 6];
+        let extra_bytes = usize::from(format.extra_bytes);
+        for point in points {
+            if format.has_gps_time {
+                point.gps_time = point.gps_time.or(Some(0.));
+            } else if point.gps_time.take().is_some() {
+                counts[0] += 1;
+            }
+            if format.has_color {
+                point.color = point.color.or_else(|| Some(Color::new(0, 0, 0)));
+            } else if point.color.take().is_some() {
+                counts[1] += 1;
+            }
+            if format.has_nir {
+                point.nir = point.nir.or(Some(0));
+            } else if point.nir.take().is_some() {
+                counts[2] += 1;
+            }
+            if format.has_waveform {
+                point.waveform = point.waveform.or_else(|| Some(Default::default()));
+            } else if point.waveform.take().is_some() {
+                counts[3] += 1;
+            }
+            if point.extra_bytes.len() > extra_bytes {
+                counts[4] += 1;
+            }
+            point.extra_bytes.resize(extra_bytes, 0);
+            if !format.is_extended && point.scanner_channel != 0 {
+                point.scanner_channel = 0;
+                counts[5] += 1;
+            }
+        }
+        let attributes = [
+            "gps time",
+            "color",
+            "nir",
+            "waveform",
+            "extra bytes",
+            "scanner channel",
+        ];
+        Ok(attributes
+            .iter()
+            .zip(&counts)
+            .filter(|&(_, &points)| points > 0)
+            .map(|(&attribute, &points)| DataLoss { attribute, points })
+            .collect())
+    }
+
+    /// Colors each point by its return number, e.g. for visualization.
+    ///
+    /// Points get the palette entry at their return number minus one, and return numbers past the
+    /// end of the palette get its last entry. If this header's point format doesn't have color, it
+    /// is switched to the matching format with color.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the palette is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Color, Header, Point};
+    /// let mut header = Header::from((1, 2));
+    /// let mut points = vec![Point { return_number: 1, ..Default::default() }];
+    /// let palette = [Color::new(65535, 0, 0), Color::new(0, 0, 65535)];
+    /// header.colorize_by_return(&mut points, &palette).unwrap();
+    /// assert_eq!(Some(palette[0]), points[0].color);
+    /// assert!(header.point_format().has_color);
+    /// ```
+    pub fn colorize_by_return(&mut self, points: &mut [Point], palette: &[Color]) -> Result<()> {
+        assert!(!palette.is_empty(), "the palette must not be empty");
+        if !self.point_format.has_color {
+            let mut builder = Builder::from(self.clone());
+            builder.point_format.has_color = true;
+            *self = builder.into_header()?;
+        }
+        for point in points {
+            let i = usize::from(point.return_number.saturating_sub(1)).min(palette.len() - 1);
+            point.color = Some(palette[i]);
+        }
+        Ok(())
+    }
+
     pub(crate) fn point_format_mut(&mut self) -> &mut Format {
         &mut self.point_format
     }
@@ -360,104 +938,534 @@ impl Header {
         &self.transforms
     }
 
-    /// Returns the bounds of this header.
+    /// Iterates over these points alongside the integer coordinates that would be written for them.
     ///
-    /// The bounds describe the min and max values in each dimension.
+    /// The integers come from this header's transforms, exactly as when writing, so this shows how
+    /// the scale and offset quantize each coordinate. A coordinate that can't be represented as an
+    /// `i32` yields an error for that point.
     ///
     /// # Examples
     ///
     /// ```
-    /// use las::Header;
-    /// let bounds = Header::default().bounds();
+    /// use las::{Header, Point, Vector};
+    /// let points = vec![Point { x: 1.2344, y: 2., z: -3., ..Default::default() }];
+    /// let header = Header::default();
+    /// let (_, raw) = header.iter_with_raw(&points).next().unwrap().unwrap();
+    /// assert_eq!(Vector { x: 1234, y: 2000, z: -3000 }, raw);
     /// ```
-    pub fn bounds(&self) -> Bounds {
-        self.bounds
+    pub fn iter_with_raw<'a>(
+        &'a self,
+        points: &'a [Point],
+    ) -> impl Iterator<Item = Result<(&'a Point, Vector<i32>)>> + 'a {
+        let transforms = self.transforms;
+        points.iter().map(move |point| {
+            Ok((
+                point,
+                Vector {
+                    x: transforms.x.inverse(point.x)?,
+                    y: transforms.y.inverse(point.y)?,
+                    z: transforms.z.inverse(point.z)?,
+                },
+            ))
+        })
     }
 
-    /// Returns this header's number of points.
+    /// Reports how precisely this header's transforms store these points' coordinates.
+    ///
+    /// Each coordinate is converted to an integer and back, as when it's written and read. The
+    /// largest change on each axis is the `max_error`, which is infinite if a coordinate can't be
+    /// stored at all. Rounding to the scale grid moves a coordinate by up to half of its axis'
+    /// scale, so the points are only `is_lossy` if a coordinate moves by more than that, e.g.
+    /// because it's too large for the transform; consider `WriteOptions::auto_offsets` or a larger
+    /// scale.
     ///
     /// # Examples
     ///
     /// ```
-    /// use las::Header;
-    /// let header = Header::default();
-    /// assert_eq!(0, header.number_of_points());
+    /// use las::{Builder, Point};
+    /// let points = vec![Point { x: 1.25, ..Default::default() }];
+    /// let mut builder = Builder::default();
+    /// builder.transforms.x.scale = 1.;
+    /// let precision = builder.clone().into_header().unwrap().coordinate_precision(&points);
+    /// assert!(!precision.is_lossy);
+    /// assert_eq!(0.25, precision.max_error.x);
+    /// builder.transforms.x.scale = 1e-12;
+    /// assert!(builder.into_header().unwrap().coordinate_precision(&points).is_lossy);
     /// ```
-    pub fn number_of_points(&self) -> u64 {
-        self.number_of_points
+    pub fn coordinate_precision(&self, points: &[Point]) -> CoordinatePrecision {
+        fn error(transform: Transform, n: f64) -> f64 {
+            transform
+                .inverse(n)
+                .map(|i| (transform.direct(i) - n).abs())
+                .unwrap_or(f64::INFINITY)
+        }
+
+        let transforms = self.transforms;
+        let mut max_error = Vector::<f64>::default();
+        for point in points {
+            max_error.x = max_error.x.max(error(transforms.x, point.x));
+            max_error.y = max_error.y.max(error(transforms.y, point.y));
+            max_error.z = max_error.z.max(error(transforms.z, point.z));
+        }
+        // Rounding error can push a coordinate that's exactly between two steps a hair past half
+        // a step.
+        let is_lossy = |error: f64, scale: f64| error > scale * (0.5 + 1e-6);
+        let is_lossy = is_lossy(max_error.x, transforms.x.scale)
+            || is_lossy(max_error.y, transforms.y.scale)
+            || is_lossy(max_error.z, transforms.z.scale);
+        CoordinatePrecision {
+            scale: Vector {
+                x: transforms.x.scale,
+                y: transforms.y.scale,
+                z: transforms.z.scale,
+            },
+            max_error,
+            is_lossy,
+        }
     }
 
-    /// Returns this header's number of points for a given return number.
+    /// Checks that this header's transforms can be used to write points.
     ///
-    /// Note that return numbers are 1-indexed.
+    /// Each scale must be positive and finite and each offset must be finite, otherwise
+    /// coordinates would silently be written as garbage integers. `Writer::new` calls this for
+    /// you.
     ///
     /// # Examples
     ///
     /// ```
-    /// use las::Header;
-    /// let header = Header::default();
-    /// assert_eq!(None, header.number_of_points_by_return(1));
+    /// use las::Builder;
+    /// let mut builder = Builder::default();
+    /// assert!(builder.clone().into_header().unwrap().validate().is_ok());
+    /// builder.transforms.x.scale = 0.;
+    /// assert!(builder.into_header().unwrap().validate().is_err());
     /// ```
-    pub fn number_of_points_by_return(&self, n: u8) -> Option<u64> {
-        self.number_of_points_by_return.get(&n).map(|&n| n)
+    pub fn validate(&self) -> Result<()> {
+        let transforms = &self.transforms;
+        for &(axis, transform) in &[
+            ('x', transforms.x),
+            ('y', transforms.y),
+            ('z', transforms.z),
+        ] {
+            if !(transform.scale > 0.
+                && transform.scale.is_finite()
+                && transform.offset.is_finite())
+            {
+                return Err(Error::InvalidScale { axis, transform }.into());
+            }
+        }
+        Ok(())
     }
 
-    /// Returns a reference to this header's vlr padding.
+    /// Checks this header and its vlrs against the las specification, returning every problem
+    /// found.
     ///
-    /// These are bytes after the vlrs but before the points. Again, not recommended for use.
+    /// Unlike `validate`, which only checks that points can be written, this is a preflight check
+    /// for files headed to strict consumers, e.g. for inverted bounds or a point format that this
+    /// header's version doesn't support. See `raw::Header::validation_issues` for the checks.
     ///
     /// # Examples
     ///
     /// ```
-    /// use las::Header;
-    /// assert!(Header::default().vlr_padding().is_empty());
+    /// use las::{Builder, Header};
+    /// use las::header::Severity;
+    /// assert!(Header::default()
+    ///     .validation_issues()
+    ///     .iter()
+    ///     .all(|issue| issue.severity == Severity::Warning));
+    /// let mut builder = Builder::default();
+    /// builder.transforms.x.scale = 0.;
+    /// let issues = builder.into_header().unwrap().validation_issues();
+    /// assert!(issues.iter().any(|issue| issue.severity == Severity::Error));
     /// ```
-    pub fn vlr_padding(&self) -> &Vec<u8> {
-        &self.vlr_padding
+    pub fn validation_issues(&self) -> Vec<ValidationIssue> {
+        let raw_header = match self.clone().into_raw() {
+            Ok(raw_header) => raw_header,
+            Err(err) => return vec![ValidationIssue::error(err.to_string())],
+        };
+        let vlrs = match self
+            .vlrs
+            .iter()
+            .map(|vlr| vlr.clone().into_raw(false))
+            .collect::<Result<Vec<_>>>()
+        {
+            Ok(vlrs) => vlrs,
+            Err(err) => return vec![ValidationIssue::error(err.to_string())],
+        };
+        raw_header.validation_issues(&vlrs)
     }
 
-    /// Returns a reference to this header's point padding.
+    /// Returns the bounds of this header.
     ///
-    /// These are the bytes after the points but before eof/any evlrs. Not recommended.
+    /// The bounds describe the min and max values in each dimension.
     ///
     /// # Examples
     ///
     /// ```
     /// use las::Header;
-    /// assert!(Header::default().point_padding().is_empty());
+    /// let bounds = Header::default().bounds();
     /// ```
-    pub fn point_padding(&self) -> &Vec<u8> {
-        &self.point_padding
+    pub fn bounds(&self) -> Bounds {
+        self.bounds
     }
 
-    /// Returns a reference to this header's vlrs.
+    /// Returns this header's bounds in another coordinate reference system.
+    ///
+    /// The corners of the bounds are transformed, so no points need to be read. See
+    /// `Bounds::transform`.
     ///
     /// # Examples
     ///
     /// ```
-    /// use las::{Vlr, Builder};
-    /// let mut builder = Builder::default();
-    /// builder.vlrs.push(Vlr::default());
-    /// let header = builder.into_header().unwrap();
-    /// assert_eq!(1, header.vlrs().len());
+    /// use las::{Header, Vector};
+    /// let header = Header::from_path("tests/data/autzen.las").unwrap();
+    /// assert_eq!(header.bounds(), header.bounds_in(&|v: Vector<f64>| v));
     /// ```
-    pub fn vlrs(&self) -> &Vec<Vlr> {
-        &self.vlrs
-    }
-
-    /// Used by the CompressedPointWriter to add the Laszip Vlr
-    #[cfg(feature = "laz")]
-    pub(crate) fn vlrs_mut(&mut self) -> &mut Vec<Vlr> {
-        &mut self.vlrs
+    pub fn bounds_in<T: CoordinateTransform>(&self, t: &T) -> Bounds {
+        self.bounds.transform(t)
     }
 
-    /// Returns a reference to header's extended variable length records.
+    /// Returns the average number of points per unit of area in this header's xy bounds.
+    ///
+    /// Returns zero if the bounds have no area, e.g. if there are no points. For irregular
+    /// footprints, `Point::density_from_hull` is more accurate.
     ///
     /// # Examples
     ///
     /// ```
-    /// use las::{Vlr, Builder};
-    /// let mut builder = Builder::from((1, 4));
+    /// use las::{Header, Point};
+    /// let mut header = Header::default();
+    /// assert_eq!(0., header.point_density());
+    /// header.add_point(&Point { x: 0., y: 0., ..Default::default() });
+    /// header.add_point(&Point { x: 2., y: 1., ..Default::default() });
+    /// assert_eq!(1., header.point_density());
+    /// ```
+    pub fn point_density(&self) -> f64 {
+        let area =
+            (self.bounds.max.x - self.bounds.min.x) * (self.bounds.max.y - self.bounds.min.y);
+        if area > 0. && area.is_finite() {
+            self.number_of_points as f64 / area
+        } else {
+            0.
+        }
+    }
+
+    /// Clamps each point's coordinates into this header's bounds, returning how many points moved.
+    ///
+    /// This is useful after a reprojection has nudged a few points past the stated bounds, since
+    /// some tools reject such points. Axes whose bounds are inverted, e.g. for a header without
+    /// points, are left alone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Header, Point};
+    /// let mut header = Header::default();
+    /// header.add_point(&Point { x: 0., ..Default::default() });
+    /// header.add_point(&Point { x: 10., ..Default::default() });
+    /// let mut points = vec![Point { x: 10.001, ..Default::default() }];
+    /// assert_eq!(1, header.clamp_to_bounds(&mut points));
+    /// assert_eq!(10., points[0].x);
+    /// ```
+    pub fn clamp_to_bounds(&self, points: &mut [Point]) -> usize {
+        fn clamp(value: &mut f64, min: f64, max: f64) -> bool {
+            if min > max {
+                false
+            } else if *value < min {
+                *value = min;
+                true
+            } else if *value > max {
+                *value = max;
+                true
+            } else {
+                false
+            }
+        }
+
+        let Bounds { min, max } = self.bounds;
+        let mut n = 0;
+        for point in points {
+            let x = clamp(&mut point.x, min.x, max.x);
+            let y = clamp(&mut point.y, min.y, max.y);
+            let z = clamp(&mut point.z, min.z, max.z);
+            if x || y || z {
+                n += 1;
+            }
+        }
+        n
+    }
+
+    /// Snaps each point's coordinates to this header's scale grid.
+    ///
+    /// Coordinates are rounded to the values they'll have after being written with this header's
+    /// transforms, so points compare exactly equal to the points read back from the file. If any
+    /// coordinate can't be represented with these transforms, an error is returned and no points
+    /// are changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Header, Point};
+    /// let header = Header::default();
+    /// let mut points = vec![Point { x: 1.23456, ..Default::default() }];
+    /// header.snap_to_scale_grid(&mut points).unwrap();
+    /// assert_eq!(1.235, points[0].x);
+    /// ```
+    pub fn snap_to_scale_grid(&self, points: &mut [Point]) -> Result<()> {
+        let transforms = &self.transforms;
+        let snapped = points
+            .iter()
+            .map(|point| {
+                Ok((
+                    transforms.x.direct(transforms.x.inverse(point.x)?),
+                    transforms.y.direct(transforms.y.inverse(point.y)?),
+                    transforms.z.direct(transforms.z.inverse(point.z)?),
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        for (point, (x, y, z)) in points.iter_mut().zip(snapped) {
+            point.x = x;
+            point.y = y;
+            point.z = z;
+        }
+        Ok(())
+    }
+
+    /// Returns this header's number of points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Header;
+    /// let header = Header::default();
+    /// assert_eq!(0, header.number_of_points());
+    /// ```
+    pub fn number_of_points(&self) -> u64 {
+        self.number_of_points
+    }
+
+    /// Returns this header's number of points for a given return number.
+    ///
+    /// Note that return numbers are 1-indexed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Header;
+    /// let header = Header::default();
+    /// assert_eq!(None, header.number_of_points_by_return(1));
+    /// ```
+    pub fn number_of_points_by_return(&self, n: u8) -> Option<u64> {
+        self.number_of_points_by_return.get(&n).map(|&n| n)
+    }
+
+    /// Counts the given points by return number, e.g. to check this header's declared counts.
+    ///
+    /// The array is sized for las 1.4, so index zero is return number one. Return numbers greater
+    /// than five are only counted if this header's point format is extended, and return number
+    /// zero isn't counted. The declared counts are returned by `number_of_points_by_return`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Header, Point};
+    /// let header = Header::default();
+    /// let points = vec![Point { return_number: 2, ..Default::default() }];
+    /// assert_eq!([0, 1, 0, 0, 0], header.points_by_return(&points)[0..5]);
+    /// assert_eq!(None, header.number_of_points_by_return(2));
+    /// ```
+    pub fn points_by_return(&self, points: &[Point]) -> [u64; 15] {
+        let max = if self.point_format.is_extended { 15 } else { 5 };
+        let mut points_by_return = [0; 15];
+        for point in points {
+            if point.return_number > 0 && point.return_number <= max {
+                points_by_return[point.return_number as usize - 1] += 1;
+            }
+        }
+        points_by_return
+    }
+
+    /// Returns the indices of the points whose return numbers are inconsistent.
+    ///
+    /// A point is flagged if its return number is zero, if its return number is greater than its
+    /// number of returns, or if its number of returns is greater than five and this header's
+    /// point format is not extended. The points are not changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Header, Point};
+    /// let points = vec![
+    ///     Point { return_number: 1, number_of_returns: 2, ..Default::default() },
+    ///     Point { return_number: 3, number_of_returns: 2, ..Default::default() },
+    /// ];
+    /// assert_eq!(vec![1], Header::default().validate_returns(&points));
+    /// ```
+    pub fn validate_returns(&self, points: &[Point]) -> Vec<usize> {
+        points
+            .iter()
+            .enumerate()
+            .filter(|&(_, point)| self.has_inconsistent_returns(point))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    pub(crate) fn has_inconsistent_returns(&self, point: &Point) -> bool {
+        let max_number_of_returns = if self.point_format.is_extended { 15 } else { 5 };
+        point.return_number == 0
+            || point.return_number > point.number_of_returns
+            || point.number_of_returns > max_number_of_returns
+    }
+
+    /// Returns the number of bytes an uncompressed las file with this header would take.
+    ///
+    /// This is the header, vlrs, and padding, plus one record for each of this header's points,
+    /// plus any point padding and evlrs. Compressed point data can't be sized in advance, so the
+    /// estimate for a compressed header is the size of the uncompressed file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Header;
+    /// let header = Header::from_path("tests/data/autzen.las").unwrap();
+    /// let len = std::fs::metadata("tests/data/autzen.las").unwrap().len();
+    /// assert_eq!(len, header.estimated_size());
+    /// ```
+    pub fn estimated_size(&self) -> u64 {
+        let vlrs = self
+            .vlrs
+            .iter()
+            .fold(0, |acc, vlr| acc + vlr.len(false) as u64);
+        let evlrs = self
+            .evlrs
+            .iter()
+            .fold(0, |acc, evlr| acc + evlr.len(true) as u64);
+        u64::from(self.version.header_size())
+            + self.padding.len() as u64
+            + vlrs
+            + self.vlr_padding.len() as u64
+            + self.point_data_len()
+            + self.point_padding.len() as u64
+            + evlrs
+    }
+
+    /// Computes a stable 64-bit hash of these points' data as it would be stored with this header.
+    ///
+    /// The coordinates are hashed after they are converted to integers with this header's
+    /// transforms, so the hash doesn't depend on tiny floating point differences that disappear
+    /// when the points are written. The hash uses FNV-1a, so it is the same across platforms and
+    /// runs, and it depends on the order of the points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Header, Point};
+    /// let header = Header::default();
+    /// let a = Point { x: 1., ..Default::default() };
+    /// let b = Point { x: 1.0000001, ..Default::default() };
+    /// let c = Point { x: 2., ..Default::default() };
+    /// assert_eq!(header.point_data_hash(&[a.clone()]).unwrap(), header.point_data_hash(&[b]).unwrap());
+    /// assert_ne!(header.point_data_hash(&[a]).unwrap(), header.point_data_hash(&[c]).unwrap());
+    /// ```
+    pub fn point_data_hash<'a, I: IntoIterator<Item = &'a Point>>(&self, points: I) -> Result<u64> {
+        use utils::{fnv1a, FNV_OFFSET_BASIS};
+
+        let mut hash = FNV_OFFSET_BASIS;
+        let mut bytes = Vec::new();
+        for point in points {
+            let format = Format {
+                has_gps_time: point.gps_time.is_some(),
+                has_color: point.color.is_some(),
+                has_waveform: point.waveform.is_some(),
+                has_nir: point.nir.is_some(),
+                is_extended: true,
+                extra_bytes: point.extra_bytes.len() as u16,
+                is_compressed: false,
+            };
+            bytes.clear();
+            point
+                .clone()
+                .into_raw(&self.transforms)?
+                .write_to(&mut bytes, &format)?;
+            hash = fnv1a(hash, &bytes);
+        }
+        Ok(hash)
+    }
+
+    /// Returns a reference to this header's vlr padding.
+    ///
+    /// These are bytes after the vlrs but before the points. Again, not recommended for use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Header;
+    /// assert!(Header::default().vlr_padding().is_empty());
+    /// ```
+    pub fn vlr_padding(&self) -> &Vec<u8> {
+        &self.vlr_padding
+    }
+
+    /// Returns a reference to this header's point padding.
+    ///
+    /// These are the bytes after the points but before eof/any evlrs, e.g. user data. Without
+    /// evlrs, they're only read by a `Reader`, only from uncompressed files (the end of compressed
+    /// point data isn't known from the header), and only up to 1 MiB. Not recommended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Header;
+    /// assert!(Header::default().point_padding().is_empty());
+    /// ```
+    pub fn point_padding(&self) -> &Vec<u8> {
+        &self.point_padding
+    }
+
+    /// Returns the offset to the first waveform data packet record, if there is one.
+    ///
+    /// This is only set for las 1.3 and later files that store their waveform data internally.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Builder;
+    /// let mut builder = Builder::from((1, 3));
+    /// builder.start_of_waveform_data_packet_record = Some(1000);
+    /// let header = builder.into_header().unwrap();
+    /// assert_eq!(Some(1000), header.start_of_waveform_data_packet_record());
+    /// ```
+    pub fn start_of_waveform_data_packet_record(&self) -> Option<u64> {
+        self.start_of_waveform_data_packet_record
+    }
+
+    /// Returns a reference to this header's vlrs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Vlr, Builder};
+    /// let mut builder = Builder::default();
+    /// builder.vlrs.push(Vlr::default());
+    /// let header = builder.into_header().unwrap();
+    /// assert_eq!(1, header.vlrs().len());
+    /// ```
+    pub fn vlrs(&self) -> &Vec<Vlr> {
+        &self.vlrs
+    }
+
+    /// Used by the CompressedPointWriter to add the Laszip Vlr
+    #[cfg(feature = "laz")]
+    pub(crate) fn vlrs_mut(&mut self) -> &mut Vec<Vlr> {
+        &mut self.vlrs
+    }
+
+    /// Returns a reference to header's extended variable length records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Vlr, Builder};
+    /// let mut builder = Builder::from((1, 4));
     /// builder.evlrs.push(Vlr::default());
     /// let header = builder.into_header().unwrap();
     /// assert_eq!(1, header.evlrs().len());
@@ -466,6 +1474,36 @@ impl Header {
         &self.evlrs
     }
 
+    /// Removes all of this header's vlrs and evlrs, e.g. to anonymize or minimize a file.
+    ///
+    /// The offset to the point data written for this header shrinks accordingly. Any point padding
+    /// after the points is kept. Dropping the extra bytes schema vlr doesn't drop the extra bytes
+    /// themselves, which are left undescribed; pass the points to remove their extra bytes too, and
+    /// to set this header's extra bytes length to zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Header, Point};
+    /// let mut header = Header::default();
+    /// let mut points = vec![Point::default()];
+    /// header.attach_attribute(&mut points, "hag", &[1.5]).unwrap();
+    /// header.clear_vlrs(Some(&mut points));
+    /// assert!(header.vlrs().is_empty());
+    /// assert_eq!(0, header.point_format().extra_bytes);
+    /// assert!(points[0].extra_bytes.is_empty());
+    /// ```
+    pub fn clear_vlrs(&mut self, points: Option<&mut [Point]>) {
+        self.vlrs.clear();
+        self.evlrs.clear();
+        if let Some(points) = points {
+            self.point_format.extra_bytes = 0;
+            for point in points {
+                point.extra_bytes.clear();
+            }
+        }
+    }
+
     /// Returns an iterator over all this header's vlrs, both extended and regular.
     ///
     /// # Examples
@@ -481,6 +1519,379 @@ impl Header {
         Vlrs(self.vlrs.iter().chain(&self.evlrs))
     }
 
+    /// Returns the first vlr or evlr with the given user id and record id.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Vlr, Builder};
+    /// let mut builder = Builder::from((1, 4));
+    /// builder.vlrs.push(Vlr { user_id: "LASF_Projection".to_string(), record_id: 2112, ..Default::default() });
+    /// let header = builder.into_header().unwrap();
+    /// assert!(header.find_vlr("LASF_Projection", 2112).is_some());
+    /// assert!(header.find_vlr("LASF_Projection", 34735).is_none());
+    /// ```
+    pub fn find_vlr(&self, user_id: &str, record_id: u16) -> Option<&Vlr> {
+        self.all_vlrs()
+            .find(|vlr| vlr.user_id == user_id && vlr.record_id == record_id)
+    }
+
+    /// Returns a mutable reference to the first vlr or evlr with the given user id and record id.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Vlr, Builder};
+    /// let mut builder = Builder::from((1, 4));
+    /// builder.evlrs.push(Vlr { user_id: "LASF_Projection".to_string(), record_id: 2112, ..Default::default() });
+    /// let mut header = builder.into_header().unwrap();
+    /// header.find_vlr_mut("LASF_Projection", 2112).unwrap().data = b"wkt".to_vec();
+    /// assert_eq!(b"wkt", header.evlrs()[0].data.as_slice());
+    /// ```
+    pub fn find_vlr_mut(&mut self, user_id: &str, record_id: u16) -> Option<&mut Vlr> {
+        self.vlrs
+            .iter_mut()
+            .chain(&mut self.evlrs)
+            .find(|vlr| vlr.user_id == user_id && vlr.record_id == record_id)
+    }
+
+    /// Parses the classification lookup table vlr, if this header has one.
+    ///
+    /// The lookup table is stored in the "LASF_Spec" vlr with record id 0, as a sequence of 16
+    /// byte entries: a class number followed by a 15 byte description. Returns a map from class
+    /// number to description, ignoring any trailing partial entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Builder, Vlr};
+    /// let mut data = vec![42];
+    /// data.extend_from_slice(b"Power lines\0\0\0\0");
+    /// let mut builder = Builder::default();
+    /// builder.vlrs.push(Vlr { user_id: "LASF_Spec".to_string(), record_id: 0, data: data, ..Default::default() });
+    /// let header = builder.into_header().unwrap();
+    /// assert_eq!("Power lines", header.classification_lookup().unwrap()[&42]);
+    /// ```
+    pub fn classification_lookup(&self) -> Option<HashMap<u8, String>> {
+        use utils::AsLasStr;
+
+        self.find_vlr("LASF_Spec", 0).map(|vlr| {
+            vlr.data
+                .chunks_exact(16)
+                .map(|entry| (entry[0], (&entry[1..]).as_las_string_lossy()))
+                .collect()
+        })
+    }
+
+    /// Parses this header's waveform packet descriptor vlrs.
+    ///
+    /// The descriptors are stored in "LASF_Spec" vlrs or evlrs with record ids 100 through 354,
+    /// each as 26 bytes. Returns a map from descriptor index, which is the record id minus 99 and
+    /// is what each point's waveform refers to, to descriptor. Vlrs that are too short are
+    /// skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Builder, Vlr};
+    /// let mut data = vec![0; 26];
+    /// data[0] = 8;
+    /// data[2] = 60;
+    /// let mut builder = Builder::from((1, 4));
+    /// builder.vlrs.push(Vlr { user_id: "LASF_Spec".to_string(), record_id: 100, data: data, ..Default::default() });
+    /// let header = builder.into_header().unwrap();
+    /// let descriptor = header.waveform_descriptors()[&1];
+    /// assert_eq!(8, descriptor.bits_per_sample);
+    /// assert_eq!(60, descriptor.number_of_samples);
+    /// ```
+    pub fn waveform_descriptors(&self) -> HashMap<u8, WaveformDescriptor> {
+        use byteorder::{ByteOrder, LittleEndian};
+
+        self.all_vlrs()
+            .filter(|vlr| {
+                vlr.user_id == "LASF_Spec"
+                    && vlr.record_id >= 100
+                    && vlr.record_id <= 354
+                    && vlr.data.len() >= 26
+            })
+            .map(|vlr| {
+                let data = &vlr.data;
+                let descriptor = WaveformDescriptor {
+                    bits_per_sample: data[0],
+                    compression_type: data[1],
+                    number_of_samples: LittleEndian::read_u32(&data[2..6]),
+                    temporal_sample_spacing: LittleEndian::read_u32(&data[6..10]),
+                    digitizer_gain: LittleEndian::read_f64(&data[10..18]),
+                    digitizer_offset: LittleEndian::read_f64(&data[18..26]),
+                };
+                ((vlr.record_id - 99) as u8, descriptor)
+            })
+            .collect()
+    }
+
+    /// Returns a header that can hold the extra bytes of both this header's and `other`'s points.
+    ///
+    /// The extra bytes schemas are read from the "LASF_Spec" vlrs with record id 4. If the
+    /// schemas are the same, the merged header is a copy of this header. Otherwise this returns
+    /// `Error::IncompatibleExtraBytes`, unless `union` is set, in which case the merged schema
+    /// holds this header's fields followed by `other`'s fields that this header doesn't have. Use
+    /// `remap_extra_bytes` to lay out each header's points for the merged header. Fields that
+    /// share a name but not a type, or extra bytes without a schema, can't be merged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Builder, Vlr};
+    /// let mut descriptor = vec![0; 192];
+    /// descriptor[2] = 3; // an unsigned short
+    /// descriptor[4..9].copy_from_slice(b"label");
+    /// let mut builder = Builder::default();
+    /// builder.point_format.extra_bytes = 2;
+    /// builder.vlrs.push(Vlr { user_id: "LASF_Spec".to_string(), record_id: 4, data: descriptor, ..Default::default() });
+    /// let header = builder.into_header().unwrap();
+    /// let other = Builder::default().into_header().unwrap();
+    /// assert!(header.merge(&other, false).is_err());
+    /// let merged = header.merge(&other, true).unwrap();
+    /// assert_eq!(2, merged.point_format().extra_bytes);
+    /// ```
+    pub fn merge(&self, other: &Header, union: bool) -> Result<Header> {
+        if self.has_same_extra_bytes(other) {
+            return Ok(self.clone());
+        }
+        let fields = self.extra_bytes_fields()?;
+        let other_fields = other.extra_bytes_fields()?;
+        if fields.len() == other_fields.len()
+            && fields.iter().zip(&other_fields).all(|(a, b)| a.is_same(b))
+        {
+            return Ok(self.clone());
+        }
+        if !union {
+            return Err(Error::IncompatibleExtraBytes.into());
+        }
+        let mut merged = fields;
+        for field in other_fields {
+            match merged.iter().find(|f| f.name() == field.name()) {
+                Some(f) if f.is_same(&field) => {}
+                Some(_) => return Err(Error::IncompatibleExtraBytes.into()),
+                None => merged.push(field),
+            }
+        }
+        let mut header = self.clone();
+        header.set_extra_bytes_fields(merged)?;
+        Ok(header)
+    }
+
+    /// Rewrites the extra bytes of this header's points for the schema of a merged header.
+    ///
+    /// Each field is moved to its place in `merged`'s schema, and fields this header doesn't have
+    /// are filled with zeros. Returns `Error::IncompatibleExtraBytes` if `merged` is missing one of
+    /// this header's fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Header, Point};
+    /// let header = Header::default();
+    /// let merged = header.merge(&header, false).unwrap();
+    /// let mut points = vec![Point::default()];
+    /// header.remap_extra_bytes(&merged, &mut points).unwrap();
+    /// assert!(points[0].extra_bytes.is_empty());
+    /// ```
+    pub fn remap_extra_bytes(&self, merged: &Header, points: &mut [Point]) -> Result<()> {
+        if self.has_same_extra_bytes(merged) {
+            return Ok(());
+        }
+        let fields = self.extra_bytes_fields()?;
+        let merged_fields = merged.extra_bytes_fields()?;
+        let mut offsets = Vec::with_capacity(fields.len());
+        let mut offset = 0;
+        for field in &fields {
+            offsets.push(offset);
+            offset += field.len;
+        }
+        if fields.iter().any(|field| {
+            !merged_fields
+                .iter()
+                .any(|f| f.name() == field.name() && f.is_same(field))
+        }) {
+            return Err(Error::IncompatibleExtraBytes.into());
+        }
+        let len = usize::from(merged.point_format.extra_bytes);
+        for point in points {
+            let mut extra_bytes = Vec::with_capacity(len);
+            for merged_field in &merged_fields {
+                match fields.iter().position(|f| f.name() == merged_field.name()) {
+                    Some(i) => {
+                        let start = offsets[i];
+                        let end = start + fields[i].len;
+                        match point.extra_bytes.get(start..end) {
+                            Some(bytes) => extra_bytes.extend_from_slice(bytes),
+                            None => extra_bytes.resize(extra_bytes.len() + fields[i].len, 0),
+                        }
+                    }
+                    None => extra_bytes.resize(extra_bytes.len() + merged_field.len, 0),
+                }
+            }
+            point.extra_bytes = extra_bytes;
+        }
+        Ok(())
+    }
+
+    fn has_same_extra_bytes(&self, other: &Header) -> bool {
+        self.point_format.extra_bytes == other.point_format.extra_bytes
+            && self.find_vlr("LASF_Spec", 4).map(|vlr| &vlr.data)
+                == other.find_vlr("LASF_Spec", 4).map(|vlr| &vlr.data)
+    }
+
+    /// Adds a double precision extra bytes attribute to this header and its points, e.g. to store
+    /// a computed height above ground.
+    ///
+    /// The attribute is described in the extra bytes schema vlr ("LASF_Spec", record id 4), which
+    /// is added if this header doesn't have one, and each value is appended to its point's extra
+    /// bytes. Read the values back with `attribute`. Returns an error if there isn't one value per
+    /// point, if the name is empty, longer than 32 bytes, or already used, or if the existing
+    /// extra bytes aren't described by a schema.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Header, Point};
+    /// let mut header = Header::default();
+    /// let mut points = vec![Point::default(), Point::default()];
+    /// header.attach_attribute(&mut points, "hag", &[1.5, 2.5]).unwrap();
+    /// assert_eq!(8, header.point_format().extra_bytes);
+    /// assert_eq!(Some(2.5), header.attribute(&points[1], "hag"));
+    /// ```
+    pub fn attach_attribute(
+        &mut self,
+        points: &mut [Point],
+        name: &str,
+        values: &[f64],
+    ) -> Result<()> {
+        use byteorder::{ByteOrder, LittleEndian};
+
+        if points.len() != values.len() {
+            return Err(Error::AttributeValues {
+                points: points.len(),
+                values: values.len(),
+            }
+            .into());
+        }
+        let mut fields = self.extra_bytes_fields()?;
+        if name.is_empty() || name.len() > 32 || fields.iter().any(|field| field.name_str() == name)
+        {
+            return Err(Error::AttributeName.into());
+        }
+        let mut descriptor = vec![0; ExtraBytesField::DESCRIPTOR_LEN];
+        descriptor[2] = 10; // double
+        descriptor[4..4 + name.len()].copy_from_slice(name.as_bytes());
+        fields.push(ExtraBytesField::new(&descriptor).expect("doubles are a known type"));
+        self.set_extra_bytes_fields(fields)?;
+        let mut buf = [0; 8];
+        for (point, &value) in points.iter_mut().zip(values) {
+            LittleEndian::write_f64(&mut buf, value);
+            point.extra_bytes.extend_from_slice(&buf);
+        }
+        Ok(())
+    }
+
+    /// Returns the value of a point's extra bytes attribute, as described by this header's extra
+    /// bytes schema.
+    ///
+    /// The value is converted to `f64`, with the attribute's scale and offset applied if it has
+    /// them. Returns `None` if there's no attribute with this name, if the point's extra bytes are
+    /// too short, or if the attribute isn't a single number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Header, Point};
+    /// let mut header = Header::default();
+    /// let mut points = vec![Point::default()];
+    /// header.attach_attribute(&mut points, "hag", &[1.5]).unwrap();
+    /// assert_eq!(Some(1.5), header.attribute(&points[0], "hag"));
+    /// assert_eq!(None, header.attribute(&points[0], "intensity"));
+    /// ```
+    pub fn attribute(&self, point: &Point, name: &str) -> Option<f64> {
+        use byteorder::{ByteOrder, LittleEndian};
+
+        let fields = self.extra_bytes_fields().ok()?;
+        let mut offset = 0;
+        for field in fields {
+            if field.name_str() != name {
+                offset += field.len;
+                continue;
+            }
+            let bytes = point.extra_bytes.get(offset..offset + field.len)?;
+            let value = match field.descriptor[2] {
+                1 => f64::from(bytes[0]),
+                2 => f64::from(bytes[0] as i8),
+                3 => f64::from(LittleEndian::read_u16(bytes)),
+                4 => f64::from(LittleEndian::read_i16(bytes)),
+                5 => f64::from(LittleEndian::read_u32(bytes)),
+                6 => f64::from(LittleEndian::read_i32(bytes)),
+                7 => LittleEndian::read_u64(bytes) as f64,
+                8 => LittleEndian::read_i64(bytes) as f64,
+                9 => f64::from(LittleEndian::read_f32(bytes)),
+                10 => LittleEndian::read_f64(bytes),
+                _ => return None,
+            };
+            let options = field.descriptor[3];
+            let scale = if options & 0b1000 != 0 {
+                LittleEndian::read_f64(&field.descriptor[112..120])
+            } else {
+                1.
+            };
+            let offset = if options & 0b1_0000 != 0 {
+                LittleEndian::read_f64(&field.descriptor[136..144])
+            } else {
+                0.
+            };
+            return Some(value * scale + offset);
+        }
+        None
+    }
+
+    fn set_extra_bytes_fields(&mut self, fields: Vec<ExtraBytesField>) -> Result<()> {
+        let len = fields.iter().map(|field| field.len).sum::<usize>();
+        if len > usize::from(u16::MAX) {
+            return Err(Error::IncompatibleExtraBytes.into());
+        }
+        self.set_extra_bytes_length(len as u16)?;
+        let data = fields
+            .into_iter()
+            .flat_map(|field| field.descriptor)
+            .collect();
+        match self.find_vlr_mut("LASF_Spec", 4) {
+            Some(vlr) => vlr.data = data,
+            None => self.vlrs.push(Vlr {
+                user_id: "LASF_Spec".to_string(),
+                record_id: 4,
+                description: "Extra bytes".to_string(),
+                data,
+            }),
+        }
+        Ok(())
+    }
+
+    fn extra_bytes_fields(&self) -> Result<Vec<ExtraBytesField>> {
+        let fields = match self.find_vlr("LASF_Spec", 4) {
+            Some(vlr) => vlr
+                .data
+                .chunks_exact(ExtraBytesField::DESCRIPTOR_LEN)
+                .map(ExtraBytesField::new)
+                .collect::<Option<Vec<_>>>()
+                .ok_or(Error::IncompatibleExtraBytes)?,
+            None => Vec::new(),
+        };
+        let len = fields.iter().map(|field| field.len).sum::<usize>();
+        if len != usize::from(self.point_format.extra_bytes) {
+            // Extra bytes without a schema can't be moved around.
+            return Err(Error::IncompatibleExtraBytes.into());
+        }
+        Ok(fields)
+    }
+
     /// Converts this header into a raw header.
     ///
     /// # Examples
@@ -490,6 +1901,8 @@ impl Header {
     /// let raw_header = Header::default().into_raw().unwrap();
     /// ```
     pub fn into_raw(self) -> Result<raw::Header> {
+        let (header_size, offset_to_point_data, number_of_variable_length_records) =
+            self.offsets()?;
         Ok(raw::Header {
             file_signature: raw::LASF,
             file_source_id: self.file_source_id,
@@ -500,9 +1913,9 @@ impl Header {
             generating_software: self.generating_software_raw()?,
             file_creation_day_of_year: self.date.map_or(0, |d| d.ordinal() as u16),
             file_creation_year: self.date.map_or(0, |d| d.year() as u16),
-            header_size: self.header_size()?,
-            offset_to_point_data: self.offset_to_point_data()?,
-            number_of_variable_length_records: self.number_of_variable_length_records()?,
+            header_size,
+            offset_to_point_data,
+            number_of_variable_length_records,
             point_data_record_format: self.point_format.to_writable_u8()?,
             point_data_record_length: self.point_format.len(),
             number_of_point_records: self.number_of_points_raw()?,
@@ -519,8 +1932,7 @@ impl Header {
             min_y: self.bounds.min.y,
             max_z: self.bounds.max.z,
             min_z: self.bounds.min.z,
-            // FIXME waveforms
-            start_of_waveform_data_packet_record: None,
+            start_of_waveform_data_packet_record: self.start_of_waveform_data_packet_record,
             evlr: self.evlr()?,
             large_file: self.large_file()?,
             padding: self.padding,
@@ -554,45 +1966,28 @@ impl Header {
         Ok(generating_software)
     }
 
-    fn header_size(&self) -> Result<u16> {
-        use std::u16;
-
-        let header_size = self.version.header_size() as usize + self.padding.len();
-        if header_size > u16::MAX as usize {
-            Err(Error::TooLarge(header_size).into())
-        } else {
-            Ok(header_size as u16)
-        }
-    }
-
-    fn offset_to_point_data(&self) -> Result<u32> {
-        use std::u32;
-
-        let vlr_len = self.vlrs.iter().fold(0, |acc, vlr| acc + vlr.len(false));
-        let offset = self.header_size()? as usize + vlr_len + self.vlr_padding.len();
-        if offset > u32::MAX as usize {
-            Err(Error::OffsetToPointDataTooLarge(offset).into())
-        } else {
-            Ok(offset as u32)
-        }
+    fn offsets(&self) -> Result<(u16, u32, u32)> {
+        raw::header::compute_offsets(
+            self.version,
+            self.padding.len(),
+            self.vlrs.iter().map(|vlr| vlr.len(false)),
+            self.vlr_padding.len(),
+        )
     }
 
-    fn number_of_variable_length_records(&self) -> Result<u32> {
-        use std::u32;
-
-        let n = self.vlrs().len();
-        if n > u32::MAX as usize {
-            Err(Error::TooManyVlrs(n).into())
-        } else {
-            Ok(n as u32)
-        }
+    fn offset_to_point_data(&self) -> Result<u32> {
+        self.offsets()
+            .map(|(_, offset_to_point_data, _)| offset_to_point_data)
     }
 
     fn number_of_points_raw(&self) -> Result<u32> {
         use feature::LargeFiles;
         use std::u32;
 
-        if self.number_of_points > u64::from(u32::MAX) {
+        if self.point_format.is_extended {
+            // Extended point formats only use the 64-bit counts, per the las 1.4 spec.
+            Ok(0)
+        } else if self.number_of_points > u64::from(u32::MAX) {
             if self.version.supports::<LargeFiles>() {
                 Ok(0)
             } else {
@@ -612,6 +2007,9 @@ impl Header {
         use std::u32;
 
         let mut number_of_points_by_return = [0; 5];
+        if self.point_format.is_extended {
+            return Ok(number_of_points_by_return);
+        }
         for (&i, &n) in &self.number_of_points_by_return {
             if i > 5 {
                 if !self.version.supports::<LargeFiles>() {
@@ -697,6 +2095,7 @@ impl Default for Header {
             padding: Vec::new(),
             point_format: Default::default(),
             point_padding: Vec::new(),
+            start_of_waveform_data_packet_record: None,
             system_identifier: "las-rs".to_string(),
             transforms: Default::default(),
             version: Default::default(),
@@ -714,6 +2113,13 @@ impl<V: Into<Version>> From<V> for Header {
     }
 }
 
+impl FromIterator<Point> for Header {
+    /// Creates a header describing these points, see `Header::from_points`.
+    fn from_iter<I: IntoIterator<Item = Point>>(iter: I) -> Header {
+        Header::from_points(&iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
 impl<'a> Iterator for Vlrs<'a> {
     type Item = &'a Vlr;
     fn next(&mut self) -> Option<&'a Vlr> {
@@ -725,6 +2131,831 @@ impl<'a> Iterator for Vlrs<'a> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn convert_gps_time_roundtrip() {
+        let mut builder = Builder::from((1, 2));
+        builder.point_format = Format::new(1).unwrap();
+        let mut header = builder.into_header().unwrap();
+        let mut points = vec![Point {
+            gps_time: Some(86_400.5),
+            ..Default::default()
+        }];
+        header
+            .convert_gps_time(&mut points, GpsTimeType::Standard, 2000)
+            .unwrap();
+        assert_eq!(GpsTimeType::Standard, header.gps_time_type());
+        assert_eq!(Some(2000. * 604_800. + 86_400.5 - 1e9), points[0].gps_time);
+        header
+            .convert_gps_time(&mut points, GpsTimeType::Week, 2000)
+            .unwrap();
+        assert_eq!(GpsTimeType::Week, header.gps_time_type());
+        assert_eq!(Some(86_400.5), points[0].gps_time);
+    }
+
+    #[test]
+    fn extra_bytes_roundtrip() {
+        use std::io::Cursor;
+        use {Read, Reader, Write, Writer};
+
+        let mut header = Header::default();
+        header.set_extra_bytes_length(8).unwrap();
+        let points = (0..3u8)
+            .map(|i| Point {
+                extra_bytes: vec![i; 8],
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let mut writer = Writer::new(Cursor::new(Vec::new()), header).unwrap();
+        for point in &points {
+            writer.write(point.clone()).unwrap();
+        }
+        let mut cursor = writer.into_inner().unwrap();
+        cursor.set_position(0);
+        let mut reader = Reader::new(cursor).unwrap();
+        assert_eq!(28, reader.header().point_format().len());
+        let read = reader.points().collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(points, read);
+    }
+
+    #[test]
+    fn extra_bytes_overflow() {
+        let mut header = Header::default();
+        assert!(header.set_extra_bytes_length(u16::MAX).is_err());
+        assert_eq!(0, header.point_format().extra_bytes);
+    }
+
+    #[test]
+    fn collect_points() {
+        let header = (0..3u8)
+            .map(|i| Point {
+                return_number: i + 1,
+                ..Default::default()
+            })
+            .collect::<Header>();
+        assert_eq!(3, header.number_of_points());
+        assert_eq!(Some(1), header.number_of_points_by_return(3));
+        assert_eq!(Version::new(1, 2), header.version());
+    }
+
+    #[test]
+    fn points_by_return_disagrees_with_declared() {
+        let mut header = Header::default();
+        for _ in 0..10 {
+            header.add_point(&Point {
+                return_number: 1,
+                ..Default::default()
+            });
+        }
+        let points = vec![
+            Point {
+                return_number: 1,
+                ..Default::default()
+            },
+            Point {
+                return_number: 2,
+                ..Default::default()
+            },
+            Point {
+                return_number: 6,
+                ..Default::default()
+            },
+        ];
+        let points_by_return = header.points_by_return(&points);
+        assert_eq!([1, 1, 0, 0, 0, 0], points_by_return[0..6]);
+        assert_eq!(Some(10), header.number_of_points_by_return(1));
+    }
+
+    #[test]
+    fn clear_vlrs_keeps_point_padding() {
+        let mut builder = Builder::from((1, 4));
+        builder.point_padding = vec![1, 2, 3];
+        builder.vlrs.push(Vlr::default());
+        builder.evlrs.push(Vlr::default());
+        let mut header = builder.into_header().unwrap();
+        header.clear_vlrs(None);
+        assert_eq!(0, header.all_vlrs().count());
+        assert_eq!(&vec![1, 2, 3], header.point_padding());
+    }
+
+    #[test]
+    fn clear_vlrs_without_points_keeps_extra_bytes() {
+        let mut header = Header::default();
+        let mut points = vec![Point::default()];
+        header.attach_attribute(&mut points, "hag", &[1.5]).unwrap();
+        header.clear_vlrs(None);
+        assert_eq!(8, header.point_format().extra_bytes);
+        assert_eq!(8, points[0].extra_bytes.len());
+    }
+
+    #[test]
+    fn estimated_size_matches_written_size() {
+        use std::io::Cursor;
+        use {Write, Writer};
+
+        let mut builder = Builder::from((1, 4));
+        builder.point_format = Format::new(3).unwrap();
+        builder.padding = vec![1, 2];
+        builder.vlr_padding = vec![3];
+        builder.point_padding = vec![4, 5, 6];
+        builder.vlrs.push(Vlr {
+            data: vec![7; 10],
+            ..Default::default()
+        });
+        builder.evlrs.push(Vlr {
+            data: vec![8; 20],
+            ..Default::default()
+        });
+        let header = builder.into_header().unwrap();
+        let mut writer = Writer::new(Cursor::new(Vec::new()), header).unwrap();
+        for _ in 0..5 {
+            writer
+                .write(Point {
+                    gps_time: Some(1.),
+                    color: Some(Default::default()),
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+        let estimated_size = writer.header().estimated_size();
+        let cursor = writer.into_inner().unwrap();
+        assert_eq!(cursor.get_ref().len() as u64, estimated_size);
+    }
+
+    #[test]
+    fn split_into_tiles() {
+        let mut points = Vec::new();
+        for &(x, y) in &[
+            (0., 0.),
+            (9., 9.),
+            (10., 0.),
+            (19., 1.),
+            (0., 10.),
+            (19., 19.),
+        ] {
+            points.push(Point {
+                x,
+                y,
+                ..Default::default()
+            });
+        }
+        let tiles = Header::default().split_into_tiles(&points, 10.);
+        assert_eq!(4, tiles.len());
+        let xs = |column, row| {
+            let (ref header, ref points) = tiles[&TileCoord { column, row }];
+            assert_eq!(points.len() as u64, header.number_of_points());
+            points.iter().map(|point| point.x).collect::<Vec<_>>()
+        };
+        assert_eq!(vec![0., 9.], xs(0, 0));
+        assert_eq!(vec![10., 19.], xs(1, 0));
+        assert_eq!(vec![0.], xs(0, 1));
+        assert_eq!(vec![19.], xs(1, 1));
+        assert_eq!(
+            10.,
+            tiles[&TileCoord { column: 1, row: 0 }].0.bounds().min.x
+        );
+    }
+
+    #[test]
+    fn snap_to_scale_grid_roundtrip() {
+        use {Read, Reader, Write, Writer};
+
+        let header = Header::default();
+        let mut points: Vec<_> = (0..100)
+            .map(|i| {
+                let i = f64::from(i);
+                Point {
+                    x: i / 3.,
+                    y: i * 0.1234567,
+                    z: -i / 7.,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        header.snap_to_scale_grid(&mut points).unwrap();
+        let mut writer = Writer::new(Cursor::new(Vec::new()), header).unwrap();
+        for point in &points {
+            writer.write(point.clone()).unwrap();
+        }
+        let mut reader = Reader::new(writer.into_inner().unwrap()).unwrap();
+        let read = reader.points().collect::<Result<Vec<_>>>().unwrap();
+        for (point, read) in points.iter().zip(&read) {
+            assert_eq!(point.x.to_bits(), read.x.to_bits());
+            assert_eq!(point.y.to_bits(), read.y.to_bits());
+            assert_eq!(point.z.to_bits(), read.z.to_bits());
+        }
+        assert_eq!(points, read);
+    }
+
+    #[test]
+    fn snap_to_scale_grid_out_of_range() {
+        let header = Header::default();
+        let mut points = vec![
+            Point {
+                x: 1.23456,
+                ..Default::default()
+            },
+            Point {
+                x: 1e10,
+                ..Default::default()
+            },
+        ];
+        assert!(header.snap_to_scale_grid(&mut points).is_err());
+        assert_eq!(1.23456, points[0].x);
+    }
+
+    #[test]
+    fn waveform_descriptors() {
+        use byteorder::{ByteOrder, LittleEndian};
+
+        let mut data = vec![0; 26];
+        data[0] = 16;
+        data[1] = 1;
+        LittleEndian::write_u32(&mut data[2..6], 256);
+        LittleEndian::write_u32(&mut data[6..10], 1000);
+        LittleEndian::write_f64(&mut data[10..18], 0.5);
+        LittleEndian::write_f64(&mut data[18..26], -2.25);
+        let mut builder = Builder::from((1, 4));
+        builder.vlrs.push(Vlr {
+            user_id: "LASF_Spec".to_string(),
+            record_id: 354,
+            data: data,
+            ..Default::default()
+        });
+        builder.evlrs.push(Vlr {
+            user_id: "LASF_Spec".to_string(),
+            record_id: 101,
+            data: vec![8; 26],
+            ..Default::default()
+        });
+        builder.vlrs.push(Vlr {
+            user_id: "LASF_Spec".to_string(),
+            record_id: 102,
+            data: vec![8; 10],
+            ..Default::default()
+        });
+        builder.vlrs.push(Vlr {
+            user_id: "LASF_Spec".to_string(),
+            record_id: 355,
+            data: vec![8; 26],
+            ..Default::default()
+        });
+        let header = builder.into_header().unwrap();
+        let descriptors = header.waveform_descriptors();
+        assert_eq!(2, descriptors.len());
+        assert_eq!(
+            WaveformDescriptor {
+                bits_per_sample: 16,
+                compression_type: 1,
+                number_of_samples: 256,
+                temporal_sample_spacing: 1000,
+                digitizer_gain: 0.5,
+                digitizer_offset: -2.25,
+            },
+            descriptors[&255]
+        );
+        assert_eq!(8, descriptors[&2].bits_per_sample);
+    }
+
+    #[test]
+    fn validation_issues_broken_header() {
+        let mut header = Header::default();
+        header.add_point(&Point::default());
+        let mut raw_header = header.into_raw().unwrap();
+        raw_header.min_x = 10.;
+        raw_header.max_x = 0.;
+        raw_header.y_scale_factor = 0.;
+        let header = Header::from_raw(raw_header).unwrap();
+        let issues = header.validation_issues();
+        assert_eq!(2, issues.len());
+        assert!(issues.iter().all(|issue| issue.severity == Severity::Error));
+        assert!(issues[0].message.contains("x bounds"));
+        assert!(issues[1].message.contains("y scale"));
+    }
+
+    #[test]
+    fn clamp_to_bounds() {
+        let mut header = Header::default();
+        header.add_point(&Point {
+            x: 0.,
+            y: 0.,
+            z: 0.,
+            ..Default::default()
+        });
+        header.add_point(&Point {
+            x: 10.,
+            y: 10.,
+            z: 10.,
+            ..Default::default()
+        });
+        let mut points = vec![
+            Point {
+                x: 5.,
+                y: 5.,
+                z: 5.,
+                ..Default::default()
+            },
+            Point {
+                x: -1.,
+                y: 5.,
+                z: 11.,
+                ..Default::default()
+            },
+        ];
+        assert_eq!(1, header.clamp_to_bounds(&mut points));
+        assert_eq!((5., 5., 5.), (points[0].x, points[0].y, points[0].z));
+        assert_eq!((0., 5., 10.), (points[1].x, points[1].y, points[1].z));
+        assert_eq!(0, Header::default().clamp_to_bounds(&mut points));
+    }
+
+    #[test]
+    fn iter_with_raw() {
+        let points = vec![
+            Point {
+                x: 1.0004,
+                y: -2.0006,
+                z: 3.,
+                ..Default::default()
+            },
+            Point {
+                x: 1e10,
+                ..Default::default()
+            },
+        ];
+        let header = Header::default();
+        let transforms = header.transforms();
+        let mut iter = header.iter_with_raw(&points);
+        let (point, raw) = iter.next().unwrap().unwrap();
+        assert!((transforms.x.direct(raw.x) - point.x).abs() <= transforms.x.scale / 2.);
+        assert!((transforms.y.direct(raw.y) - point.y).abs() <= transforms.y.scale / 2.);
+        assert!((transforms.z.direct(raw.z) - point.z).abs() <= transforms.z.scale / 2.);
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn synthetic_return_numbers_roundtrip() {
+        use std::io::Cursor;
+        use {Read, Reader, Writer};
+
+        let mut builder = Builder::from((1, 4));
+        builder.has_synthetic_return_numbers = true;
+        let header = builder.into_header().unwrap();
+        assert_eq!(8, header.clone().into_raw().unwrap().global_encoding & 8);
+        let writer = Writer::new(Cursor::new(Vec::new()), header).unwrap();
+        let reader = Reader::new(writer.into_inner().unwrap()).unwrap();
+        assert!(reader.header().has_synthetic_return_numbers());
+    }
+
+    #[test]
+    fn coordinate_precision() {
+        let points = (0..10)
+            .map(|i| Point {
+                x: 1000. + f64::from(i) * 0.001,
+                y: -500.123,
+                z: 12.5,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let precision = Header::default().coordinate_precision(&points);
+        assert!(!precision.is_lossy);
+        assert_eq!(0.001, precision.scale.x);
+
+        let mut builder = Builder::default();
+        builder.transforms.x.scale = 0.01;
+        let precision = builder.into_header().unwrap().coordinate_precision(&points);
+        assert!(!precision.is_lossy);
+        assert!((precision.max_error.x - 0.005).abs() < 1e-9);
+        assert!(precision.max_error.y < 1e-9);
+    }
+
+    #[test]
+    fn coordinate_precision_quantization_is_not_lossy() {
+        let points = (0..1000)
+            .map(|i| {
+                let i = f64::from(i);
+                Point {
+                    x: 637_000. + i * 0.123_456_789,
+                    y: 851_000. - i * 0.987_654_321,
+                    z: i.sqrt(),
+                    ..Default::default()
+                }
+            })
+            .collect::<Vec<_>>();
+        let precision = Header::default().coordinate_precision(&points);
+        assert!(precision.max_error.x > 0.);
+        assert!(precision.max_error.x <= 0.0005 + 1e-9);
+        assert!(!precision.is_lossy);
+    }
+
+    #[test]
+    fn coordinate_precision_overflow() {
+        let points = vec![Point {
+            z: 1e10,
+            ..Default::default()
+        }];
+        let precision = Header::default().coordinate_precision(&points);
+        assert!(precision.is_lossy);
+        assert_eq!(f64::INFINITY, precision.max_error.z);
+    }
+
+    #[test]
+    fn start_of_waveform_data_packet_record_roundtrip() {
+        use std::io::Cursor;
+        use {Read, Reader, Writer};
+
+        let mut builder = Builder::from((1, 3));
+        builder.start_of_waveform_data_packet_record = Some(4242);
+        let header = builder.into_header().unwrap();
+        let writer = Writer::new(Cursor::new(Vec::new()), header).unwrap();
+        let reader = Reader::new(writer.into_inner().unwrap()).unwrap();
+        assert_eq!(
+            Some(4242),
+            reader.header().start_of_waveform_data_packet_record()
+        );
+    }
+
+    #[test]
+    fn start_of_waveform_data_packet_record_requires_1_3() {
+        let mut builder = Builder::from((1, 2));
+        builder.start_of_waveform_data_packet_record = Some(4242);
+        assert!(builder.into_header().is_err());
+    }
+
+    #[test]
+    fn from_bytes() {
+        use byteorder::{ByteOrder, LittleEndian};
+
+        let mut bytes = vec![0; 227];
+        bytes[0..4].copy_from_slice(b"LASF");
+        LittleEndian::write_u16(&mut bytes[4..6], 42);
+        bytes[24] = 1;
+        bytes[25] = 2;
+        bytes[26..32].copy_from_slice(b"system");
+        LittleEndian::write_u16(&mut bytes[94..96], 227);
+        LittleEndian::write_u32(&mut bytes[96..100], 227);
+        bytes[104] = 1;
+        LittleEndian::write_u16(&mut bytes[105..107], 28);
+        LittleEndian::write_u32(&mut bytes[107..111], 3);
+        LittleEndian::write_u32(&mut bytes[111..115], 3);
+        for (i, &scale) in [0.01, 0.02, 0.03].iter().enumerate() {
+            LittleEndian::write_f64(&mut bytes[131 + 8 * i..139 + 8 * i], scale);
+        }
+        LittleEndian::write_f64(&mut bytes[155..163], 1000.);
+        LittleEndian::write_f64(&mut bytes[179..187], 10.);
+        LittleEndian::write_f64(&mut bytes[187..195], -10.);
+
+        let header = Header::from_bytes(&bytes).unwrap();
+        assert_eq!(42, header.file_source_id());
+        assert_eq!(Version::new(1, 2), header.version());
+        assert_eq!("system", header.system_identifier());
+        assert_eq!(Format::new(1).unwrap(), *header.point_format());
+        assert_eq!(3, header.number_of_points());
+        assert_eq!(Some(3), header.number_of_points_by_return(1));
+        assert_eq!(0.02, header.transforms().y.scale);
+        assert_eq!(1000., header.transforms().x.offset);
+        assert_eq!(-10., header.bounds().min.x);
+        assert_eq!(10., header.bounds().max.x);
+        assert_eq!(bytes, header.to_bytes().unwrap());
+    }
+
+    #[test]
+    fn to_bytes_includes_vlrs() {
+        let mut builder = Builder::default();
+        builder.vlrs.push(Vlr {
+            data: vec![1, 2, 3],
+            ..Default::default()
+        });
+        let header = builder.into_header().unwrap();
+        let bytes = header.to_bytes().unwrap();
+        assert_eq!(227 + 54 + 3, bytes.len());
+        assert_eq!(header, Header::from_bytes(&bytes).unwrap());
+    }
+
+    #[test]
+    fn convert_to_format_reports_losses() {
+        let mut header = Header::from((1, 2));
+        header.point_format = Format::new(3).unwrap();
+        let mut points = vec![
+            Point {
+                gps_time: Some(1.),
+                color: Some(Color::new(1, 2, 3)),
+                ..Default::default()
+            };
+            2
+        ];
+        let losses = header
+            .convert_to_format(&mut points, Format::new(0).unwrap())
+            .unwrap();
+        assert_eq!(
+            vec![
+                DataLoss {
+                    attribute: "gps time",
+                    points: 2
+                },
+                DataLoss {
+                    attribute: "color",
+                    points: 2
+                },
+            ],
+            losses
+        );
+        assert_eq!(0, header.point_format().to_u8().unwrap());
+        assert!(points
+            .iter()
+            .all(|point| point.matches(header.point_format())));
+    }
+
+    #[test]
+    fn convert_to_format_adds_attributes() {
+        let mut header = Header::from((1, 2));
+        let mut points = vec![Point::default()];
+        let losses = header
+            .convert_to_format(&mut points, Format::new(3).unwrap())
+            .unwrap();
+        assert!(losses.is_empty());
+        assert!(points[0].matches(header.point_format()));
+
+        let mut header = Header::from((1, 2));
+        assert!(header
+            .convert_to_format(&mut points, Format::new(6).unwrap())
+            .is_err());
+        assert_eq!(0, header.point_format().to_u8().unwrap());
+    }
+
+    #[test]
+    fn colorize_by_return() {
+        let mut header = Header::from((1, 2));
+        let mut points = (1..4)
+            .map(|return_number| Point {
+                return_number,
+                number_of_returns: 3,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let palette = [Color::new(1, 2, 3), Color::new(4, 5, 6)];
+        header.colorize_by_return(&mut points, &palette).unwrap();
+        assert_eq!(2, header.point_format().to_u8().unwrap());
+        assert_eq!(Some(palette[0]), points[0].color);
+        assert_eq!(Some(palette[1]), points[1].color);
+        assert_eq!(Some(palette[1]), points[2].color);
+    }
+
+    #[test]
+    fn colorize_by_return_unsupported_version() {
+        let mut header = Header::from((1, 0));
+        assert!(header
+            .colorize_by_return(&mut [], &[Color::new(0, 0, 0)])
+            .is_err());
+    }
+
+    #[test]
+    fn attach_attribute_roundtrip() {
+        use std::io::Cursor;
+        use {Read, Reader, Write, Writer};
+
+        let mut header = Header::default();
+        let mut points = vec![Point::default(); 3];
+        header
+            .attach_attribute(&mut points, "hag", &[0.5, 1.25, -2.])
+            .unwrap();
+        let mut writer = Writer::new(Cursor::new(Vec::new()), header).unwrap();
+        for point in points {
+            writer.write(point).unwrap();
+        }
+        let mut reader = Reader::new(writer.into_inner().unwrap()).unwrap();
+        let points = reader.points().collect::<Result<Vec<_>>>().unwrap();
+        let header = reader.header();
+        let values = points
+            .iter()
+            .map(|point| header.attribute(point, "hag").unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(vec![0.5, 1.25, -2.], values);
+    }
+
+    #[test]
+    fn attach_attribute_errors() {
+        let mut header = Header::default();
+        let mut points = vec![Point::default()];
+        assert!(header.attach_attribute(&mut points, "hag", &[]).is_err());
+        assert!(header.attach_attribute(&mut points, "", &[1.]).is_err());
+        header.attach_attribute(&mut points, "hag", &[1.]).unwrap();
+        assert!(header.attach_attribute(&mut points, "hag", &[1.]).is_err());
+        assert_eq!(8, points[0].extra_bytes.len());
+    }
+
+    #[test]
+    fn attribute_with_scale() {
+        let mut descriptor = vec![0; 192];
+        descriptor[2] = 3;
+        descriptor[3] = 0b1000;
+        descriptor[4..7].copy_from_slice(b"hag");
+        descriptor[112..120].copy_from_slice(&0.01f64.to_le_bytes());
+        let mut builder = Builder::default();
+        builder.point_format.extra_bytes = 2;
+        builder.vlrs.push(Vlr {
+            user_id: "LASF_Spec".to_string(),
+            record_id: 4,
+            data: descriptor,
+            ..Default::default()
+        });
+        let header = builder.into_header().unwrap();
+        let point = Point {
+            extra_bytes: vec![150, 0],
+            ..Default::default()
+        };
+        assert_eq!(Some(1.5), header.attribute(&point, "hag"));
+    }
+
+    fn extra_bytes_header(names: &[&str]) -> Header {
+        let mut data = Vec::new();
+        for name in names {
+            let mut descriptor = vec![0; 192];
+            descriptor[2] = 3;
+            descriptor[4..4 + name.len()].copy_from_slice(name.as_bytes());
+            data.extend(descriptor);
+        }
+        let mut builder = Builder::default();
+        builder.point_format.extra_bytes = 2 * names.len() as u16;
+        builder.vlrs.push(Vlr {
+            user_id: "LASF_Spec".to_string(),
+            record_id: 4,
+            data: data,
+            ..Default::default()
+        });
+        builder.into_header().unwrap()
+    }
+
+    #[test]
+    fn merge_same_extra_bytes() {
+        let header = extra_bytes_header(&["a", "b"]);
+        let mut other = extra_bytes_header(&["a", "b"]);
+        other.find_vlr_mut("LASF_Spec", 4).unwrap().data[64] = 42; // a different min
+        let merged = header.merge(&other, false).unwrap();
+        assert_eq!(header, merged);
+
+        let mut points = vec![Point {
+            extra_bytes: vec![1, 2, 3, 4],
+            ..Default::default()
+        }];
+        other.remap_extra_bytes(&merged, &mut points).unwrap();
+        assert_eq!(vec![1, 2, 3, 4], points[0].extra_bytes);
+    }
+
+    #[test]
+    fn merge_different_extra_bytes() {
+        let header = extra_bytes_header(&["a", "b"]);
+        let other = extra_bytes_header(&["b", "c"]);
+        assert!(header.merge(&other, false).is_err());
+
+        let merged = header.merge(&other, true).unwrap();
+        assert_eq!(6, merged.point_format().extra_bytes);
+        let mut points = vec![Point {
+            extra_bytes: vec![1, 2, 3, 4],
+            ..Default::default()
+        }];
+        header.remap_extra_bytes(&merged, &mut points).unwrap();
+        assert_eq!(vec![1, 2, 3, 4, 0, 0], points[0].extra_bytes);
+        let mut points = vec![Point {
+            extra_bytes: vec![5, 6, 7, 8],
+            ..Default::default()
+        }];
+        other.remap_extra_bytes(&merged, &mut points).unwrap();
+        assert_eq!(vec![0, 0, 5, 6, 7, 8], points[0].extra_bytes);
+    }
+
+    #[test]
+    fn merge_extra_bytes_without_schema() {
+        let mut header = Header::default();
+        header.set_extra_bytes_length(2).unwrap();
+        assert_eq!(header, header.merge(&header, false).unwrap());
+        assert!(header.merge(&Header::default(), true).is_err());
+    }
+
+    #[test]
+    fn convert_gps_time_without_gps_time() {
+        let mut header = Header::default();
+        assert!(header
+            .convert_gps_time(&mut [], GpsTimeType::Standard, 0)
+            .is_err());
+        assert_eq!(GpsTimeType::Week, header.gps_time_type());
+    }
+
+    #[test]
+    fn split_by_point_source() {
+        let points: Vec<_> = (0..10)
+            .map(|i| Point {
+                x: f64::from(i),
+                point_source_id: if i < 7 { 42 } else { 43 },
+                ..Default::default()
+            })
+            .collect();
+        let mut builder = Builder::default();
+        builder.vlrs.push(Vlr::default());
+        let header = builder.into_header().unwrap();
+        let partitions = header.split_by_point_source(&points);
+        assert_eq!(2, partitions.len());
+        let (ref header, ref points) = partitions[&42];
+        assert_eq!(7, points.len());
+        assert_eq!(7, header.number_of_points());
+        assert_eq!(6., header.bounds().max.x);
+        assert_eq!(1, header.vlrs().len());
+        let (ref header, ref points) = partitions[&43];
+        assert_eq!(3, points.len());
+        assert_eq!(3, header.number_of_points());
+        assert_eq!(7., header.bounds().min.x);
+    }
+
+    #[test]
+    fn validate_returns() {
+        let point = |return_number, number_of_returns| Point {
+            return_number,
+            number_of_returns,
+            ..Default::default()
+        };
+        let points = vec![
+            point(1, 1),
+            point(0, 1),
+            point(2, 1),
+            point(6, 6),
+            point(5, 5),
+        ];
+        assert_eq!(vec![1, 2, 3], Header::default().validate_returns(&points));
+        let mut builder = Builder::from((1, 4));
+        builder.point_format = Format::new(6).unwrap();
+        let header = builder.into_header().unwrap();
+        assert_eq!(vec![1, 2], header.validate_returns(&points));
+    }
+
+    #[test]
+    fn validate_zero_scale() {
+        let mut builder = Builder::default();
+        builder.transforms.y.scale = 0.;
+        match builder.into_header().unwrap().validate() {
+            Err(::Error::Header(Error::InvalidScale { axis: 'y', .. })) => {}
+            other => panic!("expected an invalid y scale, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn validate_nan_offset() {
+        let mut builder = Builder::default();
+        builder.transforms.z.offset = ::std::f64::NAN;
+        match builder.into_header().unwrap().validate() {
+            Err(::Error::Header(Error::InvalidScale { axis: 'z', .. })) => {}
+            other => panic!("expected an invalid z offset, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn set_version() {
+        let mut header = Header::from((1, 2));
+        header.set_version((1, 0)).unwrap();
+        assert_eq!(Version::new(1, 0), header.version());
+        let raw_header = header.clone().into_raw().unwrap();
+        assert_eq!(Version::new(1, 0), raw_header.version);
+        assert_eq!(227, raw_header.header_size);
+
+        header.set_version((1, 4)).unwrap();
+        assert_eq!(375, header.into_raw().unwrap().header_size);
+    }
+
+    #[test]
+    fn set_version_unsupported_format() {
+        let mut builder = Builder::from((1, 4));
+        builder.point_format = Format::new(6).unwrap();
+        let mut header = builder.into_header().unwrap();
+        assert!(header.set_version((1, 2)).is_err());
+        assert_eq!(Version::new(1, 4), header.version());
+    }
+
+    #[test]
+    fn classification_lookup() {
+        let mut data = vec![1];
+        data.extend_from_slice(b"Unclassified\0\0\0");
+        data.push(64);
+        data.extend_from_slice(b"Vendor class 64");
+        let mut builder = Builder::default();
+        builder.vlrs.push(Vlr {
+            user_id: "LASF_Spec".to_string(),
+            record_id: 0,
+            data: data,
+            ..Default::default()
+        });
+        let lookup = builder
+            .into_header()
+            .unwrap()
+            .classification_lookup()
+            .unwrap();
+        assert_eq!(2, lookup.len());
+        assert_eq!("Unclassified", lookup[&1]);
+        assert_eq!("Vendor class 64", lookup[&64]);
+    }
+
+    #[test]
+    fn classification_lookup_missing() {
+        assert!(Header::default().classification_lookup().is_none());
+    }
+
     #[test]
     fn number_of_points_by_return_zero_return_number() {
         let mut header = Header::default();
@@ -803,7 +3034,7 @@ mod tests {
     }
 
     #[test]
-    fn prefer_legacy_fields() {
+    fn prefer_large_file_fields() {
         let mut raw_header = raw::Header::default();
         raw_header.version = (1, 4).into();
         raw_header.number_of_point_records = 42;
@@ -813,10 +3044,41 @@ mod tests {
         large_file.number_of_points_by_return[0] = 43;
         raw_header.large_file = Some(large_file);
         let header = Header::from_raw(raw_header).unwrap();
+        assert_eq!(43, header.number_of_points());
+        assert_eq!(43, header.number_of_points_by_return(1).unwrap());
+    }
+
+    #[test]
+    fn fall_back_to_legacy_fields() {
+        let mut raw_header = raw::Header::default();
+        raw_header.version = (1, 4).into();
+        raw_header.number_of_point_records = 42;
+        raw_header.number_of_points_by_return[0] = 42;
+        raw_header.large_file = Some(raw::header::LargeFile::default());
+        let header = Header::from_raw(raw_header).unwrap();
         assert_eq!(42, header.number_of_points());
         assert_eq!(42, header.number_of_points_by_return(1).unwrap());
     }
 
+    #[test]
+    fn zero_legacy_fields_for_extended_formats() {
+        let mut builder = Builder::from((1, 4));
+        builder.point_format = Format::new(6).unwrap();
+        let mut header = builder.into_header().unwrap();
+        for _ in 0..42 {
+            header.add_point(&Point {
+                return_number: 1,
+                ..Default::default()
+            });
+        }
+        let raw_header = header.into_raw().unwrap();
+        assert_eq!(0, raw_header.number_of_point_records);
+        assert_eq!([0; 5], raw_header.number_of_points_by_return);
+        let large_file = raw_header.large_file.unwrap();
+        assert_eq!(42, large_file.number_of_point_records);
+        assert_eq!(42, large_file.number_of_points_by_return[0]);
+    }
+
     #[test]
     fn number_of_points_large() {
         use std::u32;
@@ -0,0 +1,187 @@
+use super::{position, squared_distance};
+use std::cmp::Ordering;
+use {Point, Vector};
+
+/// A kd-tree over point coordinates, for k-nearest-neighbor queries.
+///
+/// The tree is stored implicitly: each subrange of `order` is a subtree whose root is its middle
+/// element, split along x, y, and z in turn.
+#[derive(Clone, Debug)]
+pub struct KdTree {
+    positions: Vec<Vector<f64>>,
+    order: Vec<usize>,
+}
+
+impl KdTree {
+    /// Builds a kd-tree over these points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Point;
+    /// use las::index::KdTree;
+    /// let points = vec![Point::default(); 10];
+    /// let kdtree = KdTree::new(&points);
+    /// ```
+    pub fn new(points: &[Point]) -> KdTree {
+        let positions: Vec<_> = points.iter().map(position).collect();
+        let mut order: Vec<_> = (0..points.len()).collect();
+        build(&mut order, &positions, 0);
+        KdTree { positions, order }
+    }
+
+    /// Returns the `k` nearest points to `query` as indices and squared distances.
+    ///
+    /// Neighbors are sorted by ascending distance. Fewer than `k` neighbors are returned if
+    /// there are fewer than `k` points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Point, Vector};
+    /// use las::index::KdTree;
+    /// let points = vec![
+    ///     Point { x: 0., ..Default::default() },
+    ///     Point { x: 1., ..Default::default() },
+    ///     Point { x: 3., ..Default::default() },
+    /// ];
+    /// let kdtree = KdTree::new(&points);
+    /// assert_eq!(vec![(2, 0.25), (1, 2.25)], kdtree.knn(Vector { x: 2.5, y: 0., z: 0. }, 2));
+    /// ```
+    pub fn knn(&self, query: Vector<f64>, k: usize) -> Vec<(usize, f64)> {
+        let mut neighbors = Vec::with_capacity(k + 1);
+        if k > 0 {
+            self.search(0, self.order.len(), 0, query, k, &mut neighbors);
+        }
+        neighbors
+    }
+
+    fn search(
+        &self,
+        start: usize,
+        end: usize,
+        depth: usize,
+        query: Vector<f64>,
+        k: usize,
+        neighbors: &mut Vec<(usize, f64)>,
+    ) {
+        if start >= end {
+            return;
+        }
+        let middle = (start + end) / 2;
+        let index = self.order[middle];
+        let position = self.positions[index];
+        let distance = squared_distance(position, query);
+        if neighbors.len() < k || distance < neighbors[neighbors.len() - 1].1 {
+            let i = neighbors
+                .iter()
+                .position(|&(_, d)| distance < d)
+                .unwrap_or(neighbors.len());
+            neighbors.insert(i, (index, distance));
+            neighbors.truncate(k);
+        }
+        let difference = coordinate(query, depth) - coordinate(position, depth);
+        let (near, far) = if difference < 0. {
+            ((start, middle), (middle + 1, end))
+        } else {
+            ((middle + 1, end), (start, middle))
+        };
+        self.search(near.0, near.1, depth + 1, query, k, neighbors);
+        if neighbors.len() < k || difference * difference < neighbors[neighbors.len() - 1].1 {
+            self.search(far.0, far.1, depth + 1, query, k, neighbors);
+        }
+    }
+}
+
+fn build(order: &mut [usize], positions: &[Vector<f64>], depth: usize) {
+    if order.len() <= 1 {
+        return;
+    }
+    let middle = order.len() / 2;
+    order.select_nth_unstable_by(middle, |&a, &b| {
+        coordinate(positions[a], depth)
+            .partial_cmp(&coordinate(positions[b], depth))
+            .unwrap_or(Ordering::Equal)
+    });
+    let (left, right) = order.split_at_mut(middle);
+    build(left, positions, depth + 1);
+    build(&mut right[1..], positions, depth + 1);
+}
+
+fn coordinate(position: Vector<f64>, depth: usize) -> f64 {
+    match depth % 3 {
+        0 => position.x,
+        1 => position.y,
+        _ => position.z,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: f64, y: f64, z: f64) -> Point {
+        Point {
+            x,
+            y,
+            z,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn nearest_neighbor() {
+        let points = vec![
+            point(0., 0., 0.),
+            point(10., 0., 0.),
+            point(0., 10., 0.),
+            point(0., 0., 10.),
+            point(9., 9., 9.),
+        ];
+        let kdtree = KdTree::new(&points);
+        let neighbors = kdtree.knn(
+            Vector {
+                x: 8.,
+                y: 8.,
+                z: 8.,
+            },
+            1,
+        );
+        assert_eq!(vec![(4, 3.)], neighbors);
+    }
+
+    #[test]
+    fn knn_matches_brute_force() {
+        let points: Vec<_> = (0..500)
+            .map(|i| {
+                let i = f64::from(i);
+                point((i * 7.3) % 17., (i * 3.1) % 13., (i * 5.7) % 11.)
+            })
+            .collect();
+        let kdtree = KdTree::new(&points);
+        let query = Vector {
+            x: 8.2,
+            y: 4.1,
+            z: 6.3,
+        };
+        let mut expected: Vec<_> = points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (i, squared_distance(position(p), query)))
+            .collect();
+        expected.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        let neighbors = kdtree.knn(query, 10);
+        assert_eq!(10, neighbors.len());
+        for (neighbor, expected) in neighbors.iter().zip(&expected) {
+            assert_eq!(expected.1, neighbor.1);
+        }
+    }
+
+    #[test]
+    fn fewer_points_than_k() {
+        let points = vec![point(0., 0., 0.), point(1., 0., 0.)];
+        let kdtree = KdTree::new(&points);
+        assert_eq!(2, kdtree.knn(Vector::default(), 5).len());
+        assert!(kdtree.knn(Vector::default(), 0).is_empty());
+    }
+}
@@ -0,0 +1,128 @@
+//! Spatial indices over points.
+//!
+//! Indices are built once from a slice of points and then answer repeated neighborhood queries.
+//! Query results are indices into the slice that the index was built from. An `Octree` answers
+//! radius queries, and a `KdTree` answers k-nearest-neighbor queries, which
+//! `remove_statistical_outliers` uses to filter out isolated points.
+//!
+//! ```
+//! use las::{Point, Vector};
+//! use las::index::Octree;
+//! let points = vec![
+//!     Point { x: 0., y: 0., z: 0., ..Default::default() },
+//!     Point { x: 5., y: 0., z: 0., ..Default::default() },
+//! ];
+//! let octree = Octree::new(&points, 8);
+//! assert_eq!(vec![0], octree.query_radius(Vector { x: 0., y: 0., z: 0. }, 1.));
+//! ```
+
+mod kdtree;
+mod octree;
+
+pub use self::kdtree::KdTree;
+pub use self::octree::Octree;
+
+use {Point, Vector};
+
+/// Removes isolated points, returning the points that remain.
+///
+/// For each point, computes the mean distance to its `k` nearest neighbors. Points whose mean
+/// distance is more than `std_ratio` standard deviations above the mean of those distances are
+/// removed. The remaining points keep their order. If there are not more than `k` points, they are
+/// all kept.
+///
+/// # Examples
+///
+/// ```
+/// use las::Point;
+/// use las::index::remove_statistical_outliers;
+/// let mut points: Vec<_> = (0..10)
+///     .map(|i| Point { x: f64::from(i), ..Default::default() })
+///     .collect();
+/// points.push(Point { x: 1000., ..Default::default() });
+/// let points = remove_statistical_outliers(&points, 3, 1.);
+/// assert_eq!(10, points.len());
+/// ```
+pub fn remove_statistical_outliers(points: &[Point], k: usize, std_ratio: f64) -> Vec<Point> {
+    if k == 0 || points.len() <= k {
+        return points.to_vec();
+    }
+    let kdtree = KdTree::new(points);
+    let mean_distances: Vec<f64> = points
+        .iter()
+        .enumerate()
+        .map(|(i, point)| {
+            let neighbors = kdtree.knn(position(point), k + 1);
+            let sum: f64 = neighbors
+                .iter()
+                .filter(|&&(index, _)| index != i)
+                .take(k)
+                .map(|&(_, distance)| distance.sqrt())
+                .sum();
+            sum / k as f64
+        })
+        .collect();
+    let n = mean_distances.len() as f64;
+    let mean = mean_distances.iter().sum::<f64>() / n;
+    let variance = mean_distances
+        .iter()
+        .map(|distance| (distance - mean).powi(2))
+        .sum::<f64>()
+        / n;
+    let threshold = mean + std_ratio * variance.sqrt();
+    points
+        .iter()
+        .zip(mean_distances)
+        .filter(|&(_, distance)| distance <= threshold)
+        .map(|(point, _)| point.clone())
+        .collect()
+}
+
+fn position(point: &Point) -> Vector<f64> {
+    Vector {
+        x: point.x,
+        y: point.y,
+        z: point.z,
+    }
+}
+
+fn squared_distance(a: Vector<f64>, b: Vector<f64>) -> f64 {
+    (a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_isolated_outlier() {
+        let mut points = Vec::new();
+        for x in 0..5 {
+            for y in 0..5 {
+                for z in 0..5 {
+                    points.push(Point {
+                        x: f64::from(x),
+                        y: f64::from(y),
+                        z: f64::from(z),
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+        points.push(Point {
+            x: 100.,
+            y: 100.,
+            z: 100.,
+            ..Default::default()
+        });
+        let filtered = remove_statistical_outliers(&points, 8, 2.);
+        assert_eq!(125, filtered.len());
+        assert!(filtered.iter().all(|point| point.x < 100.));
+    }
+
+    #[test]
+    fn too_few_points() {
+        let points = vec![Point::default(); 3];
+        assert_eq!(3, remove_statistical_outliers(&points, 3, 1.).len());
+    }
+}
@@ -0,0 +1,219 @@
+use super::{position, squared_distance};
+use {Bounds, Point, Vector};
+
+/// Leaves are not split past this depth, so coincident points can't recurse forever.
+const MAX_DEPTH: usize = 21;
+
+/// An octree over point coordinates, for repeated radius queries.
+#[derive(Clone, Debug)]
+pub struct Octree {
+    positions: Vec<Vector<f64>>,
+    root: Node,
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    bounds: Bounds,
+    indices: Vec<usize>,
+    children: Vec<Node>,
+}
+
+impl Octree {
+    /// Builds an octree over these points.
+    ///
+    /// Nodes holding more than `max_points_per_leaf` points are split into eight children.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Point;
+    /// use las::index::Octree;
+    /// let points = vec![Point::default(); 10];
+    /// let octree = Octree::new(&points, 4);
+    /// ```
+    pub fn new(points: &[Point], max_points_per_leaf: usize) -> Octree {
+        let mut bounds = Bounds::default();
+        for point in points {
+            bounds.grow(point);
+        }
+        let positions: Vec<_> = points.iter().map(position).collect();
+        let root = Node::new(
+            bounds,
+            (0..points.len()).collect(),
+            &positions,
+            max_points_per_leaf.max(1),
+            0,
+        );
+        Octree { positions, root }
+    }
+
+    /// Returns the indices of all points within `radius` of `center`, in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Point, Vector};
+    /// use las::index::Octree;
+    /// let points = vec![
+    ///     Point { x: 0., ..Default::default() },
+    ///     Point { x: 1., ..Default::default() },
+    ///     Point { x: 3., ..Default::default() },
+    /// ];
+    /// let octree = Octree::new(&points, 1);
+    /// assert_eq!(vec![0, 1], octree.query_radius(Vector { x: 0.5, y: 0., z: 0. }, 1.));
+    /// ```
+    pub fn query_radius(&self, center: Vector<f64>, radius: f64) -> Vec<usize> {
+        let mut indices = Vec::new();
+        self.root
+            .query_radius(&self.positions, center, radius, &mut indices);
+        indices.sort_unstable();
+        indices
+    }
+}
+
+impl Node {
+    fn new(
+        bounds: Bounds,
+        indices: Vec<usize>,
+        positions: &[Vector<f64>],
+        max_points_per_leaf: usize,
+        depth: usize,
+    ) -> Node {
+        if indices.len() <= max_points_per_leaf || depth >= MAX_DEPTH {
+            return Node {
+                bounds,
+                indices,
+                children: Vec::new(),
+            };
+        }
+        let center = bounds.center();
+        let mut octants = vec![Vec::new(); 8];
+        for index in indices {
+            let position = positions[index];
+            let octant = (position.x > center.x) as usize
+                | ((position.y > center.y) as usize) << 1
+                | ((position.z > center.z) as usize) << 2;
+            octants[octant].push(index);
+        }
+        let children = octants
+            .into_iter()
+            .enumerate()
+            .map(|(octant, indices)| {
+                let mut child = bounds;
+                if octant & 1 == 0 {
+                    child.max.x = center.x;
+                } else {
+                    child.min.x = center.x;
+                }
+                if octant & 2 == 0 {
+                    child.max.y = center.y;
+                } else {
+                    child.min.y = center.y;
+                }
+                if octant & 4 == 0 {
+                    child.max.z = center.z;
+                } else {
+                    child.min.z = center.z;
+                }
+                Node::new(child, indices, positions, max_points_per_leaf, depth + 1)
+            })
+            .collect();
+        Node {
+            bounds,
+            indices: Vec::new(),
+            children,
+        }
+    }
+
+    fn query_radius(
+        &self,
+        positions: &[Vector<f64>],
+        center: Vector<f64>,
+        radius: f64,
+        indices: &mut Vec<usize>,
+    ) {
+        let nearest = Vector {
+            x: center.x.max(self.bounds.min.x).min(self.bounds.max.x),
+            y: center.y.max(self.bounds.min.y).min(self.bounds.max.y),
+            z: center.z.max(self.bounds.min.z).min(self.bounds.max.z),
+        };
+        let radius_squared = radius * radius;
+        if squared_distance(nearest, center) > radius_squared {
+            return;
+        }
+        indices.extend(
+            self.indices
+                .iter()
+                .filter(|&&index| squared_distance(positions[index], center) <= radius_squared),
+        );
+        for child in &self.children {
+            child.query_radius(positions, center, radius, indices);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Vec<Point> {
+        let mut points = Vec::new();
+        for x in 0..10 {
+            for y in 0..10 {
+                for z in 0..10 {
+                    points.push(Point {
+                        x: f64::from(x),
+                        y: f64::from(y),
+                        z: f64::from(z),
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+        points
+    }
+
+    #[test]
+    fn query_radius_grid() {
+        let points = grid();
+        let octree = Octree::new(&points, 8);
+        let center = Vector {
+            x: 5.,
+            y: 5.,
+            z: 5.,
+        };
+        let indices = octree.query_radius(center, 1.);
+        assert_eq!(vec![455, 545, 554, 555, 556, 565, 655], indices);
+    }
+
+    #[test]
+    fn query_radius_matches_brute_force() {
+        let points = grid();
+        let octree = Octree::new(&points, 3);
+        let center = Vector {
+            x: 2.3,
+            y: 7.1,
+            z: 4.4,
+        };
+        let expected: Vec<_> = points
+            .iter()
+            .enumerate()
+            .filter(|&(_, point)| squared_distance(position(point), center) <= 2.5 * 2.5)
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(expected, octree.query_radius(center, 2.5));
+    }
+
+    #[test]
+    fn coincident_points() {
+        let points = vec![Point::default(); 100];
+        let octree = Octree::new(&points, 1);
+        assert_eq!(100, octree.query_radius(Vector::default(), 0.).len());
+    }
+
+    #[test]
+    fn empty() {
+        let octree = Octree::new(&[], 8);
+        assert!(octree.query_radius(Vector::default(), 1.).is_empty());
+    }
+}
@@ -150,6 +150,8 @@
 
 extern crate byteorder;
 extern crate chrono;
+#[macro_use]
+extern crate log;
 extern crate num;
 extern crate thiserror;
 extern crate uuid;
@@ -162,6 +164,8 @@ mod compression;
 
 pub mod feature;
 pub mod header;
+pub mod index;
+pub mod pipeline;
 pub mod point;
 pub mod raw;
 pub mod reader;
@@ -170,6 +174,7 @@ pub mod writer;
 
 mod bounds;
 mod color;
+mod diff;
 mod error;
 mod gps_time_type;
 mod transform;
@@ -177,19 +182,20 @@ mod utils;
 mod vector;
 mod version;
 
-pub use bounds::Bounds;
+pub use bounds::{Bounds, CoordinateTransform};
 pub use color::Color;
+pub use diff::Diff;
 pub use error::Error;
 pub use feature::Feature;
 pub use gps_time_type::GpsTimeType;
 pub use header::{Builder, Header};
 pub use point::Point;
-pub use reader::{Read, Reader};
+pub use reader::{Read, ReadWarning, Reader};
 pub use transform::Transform;
 pub use vector::Vector;
 pub use version::Version;
 pub use vlr::Vlr;
-pub use writer::{Write, Writer};
+pub use writer::{Write, WriteOptions, Writer};
 
 /// Crate-specific result type.
 pub type Result<T> = std::result::Result<T, Error>;
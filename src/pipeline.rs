@@ -0,0 +1,98 @@
+//! Stream points from one las source to another.
+//!
+//! A pipeline reads one point at a time, transforms it, and writes it, so files that don't fit in
+//! memory can be processed. The writer accumulates the point counts and bounds of the output.
+//!
+//! ```
+//! use std::io::Cursor;
+//! use las::{Read, Reader};
+//! let reader = Reader::from_path("tests/data/autzen.las").unwrap();
+//! let header = reader.header().clone();
+//! let cursor = las::pipeline::process(reader, Cursor::new(Vec::new()), header, |point| {
+//!     if point.return_number == 1 { Some(point) } else { None }
+//! })
+//! .unwrap();
+//! ```
+
+use std::fmt::Debug;
+use std::io::Seek;
+
+use {Header, Point, Read, Result, Write, Writer};
+
+/// Reads every point from `reader`, transforms it with `f`, and writes the result to `dest`.
+///
+/// Points for which `f` returns `None` are dropped. Points are never all held in memory at once.
+/// The output is written with `header`, which must describe the transformed points, and the inner
+/// `Write` is returned, seeked to the beginning of the las data. Reading stops at the first error.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Cursor;
+/// use las::{Read, Reader};
+/// use las::pipeline::process;
+/// let reader = Reader::from_path("tests/data/autzen.las").unwrap();
+/// let header = reader.header().clone();
+/// let cursor = process(reader, Cursor::new(Vec::new()), header, Some).unwrap();
+/// let reader = Reader::new(cursor).unwrap();
+/// assert_eq!(106, reader.header().number_of_points());
+/// ```
+pub fn process<R, W, F>(mut reader: R, dest: W, header: Header, mut f: F) -> Result<W>
+where
+    R: Read,
+    W: 'static + std::io::Write + Seek + Debug,
+    F: FnMut(Point) -> Option<Point>,
+{
+    let mut writer = Writer::new(dest, header)?;
+    for point in reader.points() {
+        if let Some(point) = f(point?) {
+            writer.write(point)?;
+        }
+    }
+    writer.into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Reader;
+
+    #[test]
+    fn halve_x() {
+        let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+        let header = reader.header().clone();
+        let points = reader.points().collect::<Result<Vec<_>>>().unwrap();
+
+        let reader = Reader::from_path("tests/data/autzen.las").unwrap();
+        let cursor = process(
+            reader,
+            std::io::Cursor::new(Vec::new()),
+            header,
+            |mut point| {
+                point.x /= 2.;
+                Some(point)
+            },
+        )
+        .unwrap();
+
+        let mut reader = Reader::new(cursor).unwrap();
+        let halved = reader.points().collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(points.len(), halved.len());
+        let transforms = reader.header().transforms();
+        for (point, halved) in points.iter().zip(&halved) {
+            assert!((point.x / 2. - halved.x).abs() <= transforms.x.scale);
+            assert_eq!(point.y, halved.y);
+        }
+        let min_x = points.iter().map(|p| p.x).fold(f64::INFINITY, f64::min);
+        assert!((min_x / 2. - reader.header().bounds().min.x).abs() <= transforms.x.scale);
+    }
+
+    #[test]
+    fn drop_points() {
+        let reader = Reader::from_path("tests/data/autzen.las").unwrap();
+        let header = reader.header().clone();
+        let cursor = process(reader, std::io::Cursor::new(Vec::new()), header, |_| None).unwrap();
+        let reader = Reader::new(cursor).unwrap();
+        assert_eq!(0, reader.header().number_of_points());
+    }
+}
@@ -1,5 +1,6 @@
 use point::Error;
-use Result;
+use std::collections::HashMap;
+use {Bounds, Point, Result};
 
 /// The ASPRS classification table.
 ///
@@ -91,6 +92,81 @@ impl Classification {
             64..=255 => Classification::UserDefinable(n),
         })
     }
+
+    /// Rewrites these points' classifications through a map of class numbers.
+    ///
+    /// Classes that aren't in the map are left unchanged. If any class would be mapped to an
+    /// invalid classification, an error is returned and no points are changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use las::Point;
+    /// use las::point::Classification;
+    /// let mut points = vec![Point { classification: Classification::Unclassified, ..Default::default() }];
+    /// let map: HashMap<u8, u8> = vec![(1, 2)].into_iter().collect();
+    /// Classification::remap(&mut points, &map).unwrap();
+    /// assert_eq!(Classification::Ground, points[0].classification);
+    /// ```
+    pub fn remap(points: &mut [Point], map: &HashMap<u8, u8>) -> Result<()> {
+        Classification::remap_with(points, |n| *map.get(&n).unwrap_or(&n))
+    }
+
+    /// Sets the classification of every point inside the bounds, e.g. to mark a region as ground.
+    ///
+    /// The bounds are inclusive, see `Bounds::contains`. Returns the number of points whose
+    /// classification changed, so points that already had this classification aren't counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Bounds, Point, Vector};
+    /// use las::point::Classification;
+    /// let mut points = vec![Point::default(), Point { x: 2., ..Default::default() }];
+    /// let bounds = Bounds { min: Vector { x: -1., y: -1., z: -1. }, max: Vector { x: 1., y: 1., z: 1. } };
+    /// assert_eq!(1, Classification::classify_in_bounds(&mut points, &bounds, Classification::Ground));
+    /// assert_eq!(Classification::Ground, points[0].classification);
+    /// ```
+    pub fn classify_in_bounds(
+        points: &mut [Point],
+        bounds: &Bounds,
+        classification: Classification,
+    ) -> usize {
+        let mut changed = 0;
+        for point in points.iter_mut().filter(|point| bounds.contains(point)) {
+            if point.classification != classification {
+                point.classification = classification;
+                changed += 1;
+            }
+        }
+        changed
+    }
+
+    /// Rewrites these points' classifications with a function of the class number.
+    ///
+    /// If any class would be mapped to an invalid classification, an error is returned and no
+    /// points are changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Point;
+    /// use las::point::Classification;
+    /// let mut points = vec![Point { classification: Classification::UserDefinable(70), ..Default::default() }];
+    /// Classification::remap_with(&mut points, |n| if n >= 64 { 1 } else { n }).unwrap();
+    /// assert_eq!(Classification::Unclassified, points[0].classification);
+    /// ```
+    pub fn remap_with<F: Fn(u8) -> u8>(points: &mut [Point], f: F) -> Result<()> {
+        let classifications = points
+            .iter()
+            .map(|point| Classification::new(f(point.classification.into())))
+            .collect::<Result<Vec<_>>>()?;
+        for (point, classification) in points.iter_mut().zip(classifications) {
+            point.classification = classification;
+        }
+        Ok(())
+    }
 }
 
 impl From<Classification> for u8 {
@@ -124,3 +200,81 @@ impl Default for Classification {
         Classification::CreatedNeverClassified
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn histogram(points: &[Point]) -> HashMap<u8, usize> {
+        let mut histogram = HashMap::new();
+        for point in points {
+            *histogram.entry(point.classification.into()).or_insert(0) += 1;
+        }
+        histogram
+    }
+
+    #[test]
+    fn remap() {
+        let mut points: Vec<_> = [1, 1, 7, 3, 2]
+            .iter()
+            .map(|&n| Point {
+                classification: Classification::new(n).unwrap(),
+                ..Default::default()
+            })
+            .collect();
+        let map = vec![(1, 2), (7, 9)].into_iter().collect();
+        Classification::remap(&mut points, &map).unwrap();
+        let histogram = histogram(&points);
+        assert_eq!(None, histogram.get(&1));
+        assert_eq!(Some(&3), histogram.get(&2));
+        assert_eq!(None, histogram.get(&7));
+        assert_eq!(Some(&1), histogram.get(&9));
+        assert_eq!(Some(&1), histogram.get(&3));
+    }
+
+    #[test]
+    fn classify_in_bounds() {
+        use Vector;
+
+        let mut points = Vec::new();
+        for x in 0..4 {
+            for y in 0..4 {
+                points.push(Point {
+                    x: f64::from(x),
+                    y: f64::from(y),
+                    classification: Classification::Unclassified,
+                    ..Default::default()
+                });
+            }
+        }
+        points[0].classification = Classification::Ground;
+        let quadrant = Bounds {
+            min: Vector {
+                x: 0.,
+                y: 0.,
+                z: 0.,
+            },
+            max: Vector {
+                x: 1.,
+                y: 1.,
+                z: 0.,
+            },
+        };
+        let changed =
+            Classification::classify_in_bounds(&mut points, &quadrant, Classification::Ground);
+        assert_eq!(3, changed);
+        let histogram = histogram(&points);
+        assert_eq!(Some(&4), histogram.get(&2));
+        assert_eq!(Some(&12), histogram.get(&1));
+    }
+
+    #[test]
+    fn remap_to_overlap() {
+        let mut points = vec![Point::default(); 2];
+        let map = vec![(0, 12)].into_iter().collect();
+        assert!(Classification::remap(&mut points, &map).is_err());
+        assert!(points
+            .iter()
+            .all(|point| point.classification == Classification::CreatedNeverClassified));
+    }
+}
@@ -1,7 +1,7 @@
 use std::fmt;
 
 use point::Error;
-use Result;
+use {Point, Result};
 
 const TIME_FORMATS: &'static [u8] = &[1, 3, 4, 5, 6, 7, 8, 9, 10];
 const COLOR_FORMATS: &'static [u8] = &[2, 3, 5, 7, 8, 10];
@@ -108,6 +108,50 @@ impl Format {
         }
     }
 
+    /// Infers the smallest point format that can hold all of the points' attributes.
+    ///
+    /// Gps time, color, waveforms, and near infrared are included if any point has them, and the
+    /// format is extended if any point needs the larger flags (e.g. a scanner channel or more than
+    /// seven returns). The number of extra bytes is the largest number found on any point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::point::Format;
+    /// use las::{Color, Point};
+    ///
+    /// let mut point = Point::default();
+    /// assert_eq!(0, Format::infer(&[point.clone()]).to_u8().unwrap());
+    ///
+    /// point.gps_time = Some(42.);
+    /// point.color = Some(Color::new(1, 2, 3));
+    /// assert_eq!(3, Format::infer(&[point]).to_u8().unwrap());
+    /// ```
+    pub fn infer<'a, I: IntoIterator<Item = &'a Point>>(points: I) -> Format {
+        let mut format = Format::default();
+        for point in points {
+            format.has_gps_time |= point.gps_time.is_some();
+            format.has_color |= point.color.is_some();
+            format.has_waveform |= point.waveform.is_some();
+            format.has_nir |= point.nir.is_some();
+            format.is_extended |= point.return_number > 7
+                || point.number_of_returns > 7
+                || point.scanner_channel > 0
+                || u8::from(point.classification) > 31;
+            if point.extra_bytes.len() > format.extra_bytes as usize {
+                format.extra_bytes = point.extra_bytes.len() as u16;
+            }
+        }
+        if format.has_nir {
+            format.has_color = true;
+            format.is_extended = true;
+        }
+        if format.is_extended || format.has_waveform {
+            format.has_gps_time = true;
+        }
+        format
+    }
+
     /// Converts this point format into an extended format.
     ///
     /// "Extended" formats can contain more information per point, and must have gps time.
@@ -423,6 +467,35 @@ mod tests {
         assert_eq!(21, format.len());
     }
 
+    #[test]
+    fn infer() {
+        use Color;
+
+        let points = vec![
+            Point {
+                gps_time: Some(42.),
+                ..Default::default()
+            },
+            Point {
+                color: Some(Color::new(1, 2, 3)),
+                ..Default::default()
+            },
+        ];
+        assert_eq!(Format::new(3).unwrap(), Format::infer(&points));
+        assert_eq!(Format::new(1).unwrap(), Format::infer(&points[0..1]));
+        assert_eq!(Format::new(2).unwrap(), Format::infer(&points[1..]));
+        assert_eq!(Format::new(0).unwrap(), Format::infer(&[]));
+    }
+
+    #[test]
+    fn infer_extended() {
+        let point = Point {
+            scanner_channel: 1,
+            ..Default::default()
+        };
+        assert_eq!(Format::new(6).unwrap(), Format::infer(&[point]));
+    }
+
     #[test]
     fn is_compressed() {
         let format = Format {
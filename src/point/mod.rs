@@ -22,6 +22,9 @@ pub use self::scan_direction::ScanDirection;
 
 use raw;
 use raw::point::Waveform;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
 use thiserror::Error;
 use {Color, Result, Transform, Vector};
 
@@ -107,10 +110,14 @@ pub struct Point {
     /// The channel of the scanner, used only in multi-channel systems.
     pub scanner_channel: u8,
 
-    /// The angle of the output of the laser pulse.
+    /// The angle of the output of the laser pulse, in degrees.
     ///
     /// This is supposed to include the roll of the aircraft, if applicable. Zero degrees is nadir,
     /// -90° is to the left.
+    ///
+    /// Point formats zero through five store the scan angle as a whole-degree rank, and the
+    /// extended formats store it in increments of 0.006°. Either way, it is converted to degrees
+    /// here.
     pub scan_angle: f32,
 
     /// Used at the user's discretion.
@@ -262,6 +269,396 @@ impl Point {
         }
     }
 
+    /// Returns this point's color scaled down to eight bits per channel, if it has one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Color, Point};
+    /// let mut point = Point::default();
+    /// assert_eq!(None, point.color_8bit());
+    /// point.color = Some(Color::new(65535, 32768, 0));
+    /// assert_eq!(Some((255, 128, 0)), point.color_8bit());
+    /// ```
+    pub fn color_8bit(&self) -> Option<(u8, u8, u8)> {
+        self.color.map(|color| color.to_8bit())
+    }
+
+    /// Returns an iterator over the points with this classification, without allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Point;
+    /// use las::point::Classification;
+    /// let points = vec![
+    ///     Point { classification: Classification::Ground, ..Default::default() },
+    ///     Point::default(),
+    /// ];
+    /// assert_eq!(1, Point::iter_classification(&points, Classification::Ground).count());
+    /// ```
+    pub fn iter_classification<'a>(
+        points: &'a [Point],
+        classification: Classification,
+    ) -> impl Iterator<Item = &'a Point> + 'a {
+        points
+            .iter()
+            .filter(move |point| point.classification == classification)
+    }
+
+    /// Returns copies of the points whose elevation is within `[z_min, z_max]`, e.g. for terrain
+    /// cross-sections.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Point;
+    /// let points = vec![
+    ///     Point { z: 1., ..Default::default() },
+    ///     Point { z: 5., ..Default::default() },
+    /// ];
+    /// let slice = Point::z_slice(&points, 0., 2.);
+    /// assert_eq!(1, slice.len());
+    /// ```
+    pub fn z_slice(points: &[Point], z_min: f64, z_max: f64) -> Vec<Point> {
+        points
+            .iter()
+            .filter(|point| point.z >= z_min && point.z <= z_max)
+            .cloned()
+            .collect()
+    }
+
+    /// Sets the gps time of each point to `time`.
+    ///
+    /// Use this when converting points to a point format with gps time, since every point written
+    /// in such a format must have one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Point;
+    /// let mut points = vec![Point::default(); 2];
+    /// Point::set_gps_time_constant(&mut points, 42.);
+    /// assert!(points.iter().all(|point| point.gps_time == Some(42.)));
+    /// ```
+    pub fn set_gps_time_constant(points: &mut [Point], time: f64) {
+        Point::set_gps_time_from(points, |_| time);
+    }
+
+    /// Sets the gps time of each point to `f` of the point's index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Point;
+    /// let mut points = vec![Point::default(); 2];
+    /// Point::set_gps_time_from(&mut points, |i| i as f64 * 0.5);
+    /// assert_eq!(Some(0.5), points[1].gps_time);
+    /// ```
+    pub fn set_gps_time_from<F: FnMut(usize) -> f64>(points: &mut [Point], mut f: F) {
+        for (i, point) in points.iter_mut().enumerate() {
+            point.gps_time = Some(f(i));
+        }
+    }
+
+    /// Returns true if these points are in non-decreasing order of `f`.
+    ///
+    /// Readers return points in the order they're stored and writers store them in the order
+    /// they're written, so this checks a file's order as well. Incomparable keys, e.g. NaN,
+    /// count as out of order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Point;
+    /// let points = vec![Point { z: 1., ..Default::default() }, Point { z: 2., ..Default::default() }];
+    /// assert!(Point::is_sorted_by_key(&points, |point| point.z));
+    /// assert!(!Point::is_sorted_by_key(&points, |point| -point.z));
+    /// ```
+    pub fn is_sorted_by_key<K: PartialOrd, F: FnMut(&Point) -> K>(points: &[Point], f: F) -> bool {
+        let mut keys = points.iter().map(f);
+        let mut previous = match keys.next() {
+            Some(key) => key,
+            None => return true,
+        };
+        for key in keys {
+            match previous.partial_cmp(&key) {
+                Some(Ordering::Less) | Some(Ordering::Equal) => previous = key,
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    /// Returns true if these points are in non-decreasing order of gps time.
+    ///
+    /// Points without a gps time sort before points with one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Point;
+    /// let mut points = vec![Point::default(); 3];
+    /// Point::set_gps_time_from(&mut points, |i| i as f64);
+    /// assert!(Point::is_sorted_by_gps_time(&points));
+    /// points.swap(0, 2);
+    /// assert!(!Point::is_sorted_by_gps_time(&points));
+    /// ```
+    pub fn is_sorted_by_gps_time(points: &[Point]) -> bool {
+        Point::is_sorted_by_key(points, |point| point.gps_time)
+    }
+
+    /// Removes the points that are flagged as withheld, which should be excluded from processing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Point;
+    /// let mut points = vec![Point::default(), Point { is_withheld: true, ..Default::default() }];
+    /// Point::remove_withheld(&mut points);
+    /// assert_eq!(1, points.len());
+    /// ```
+    pub fn remove_withheld(points: &mut Vec<Point>) {
+        points.retain(|point| !point.is_withheld);
+    }
+
+    /// Removes the points that are flagged as synthetic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Point;
+    /// let mut points = vec![Point::default(), Point { is_synthetic: true, ..Default::default() }];
+    /// Point::remove_synthetic(&mut points);
+    /// assert_eq!(1, points.len());
+    /// ```
+    pub fn remove_synthetic(points: &mut Vec<Point>) {
+        points.retain(|point| !point.is_synthetic);
+    }
+
+    /// Retains only the points for which `f` returns true, returning the original indices of the
+    /// retained points.
+    ///
+    /// The returned indices are in order, so `indices[i]` is where the `i`th remaining point was
+    /// before filtering. Use them to cross-reference external per-point data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Point;
+    /// let mut points = vec![Point::default(), Point { is_withheld: true, ..Default::default() }, Point::default()];
+    /// let indices = Point::retain_indexed(&mut points, |point| !point.is_withheld);
+    /// assert_eq!(vec![0, 2], indices);
+    /// assert_eq!(2, points.len());
+    /// ```
+    pub fn retain_indexed<F: FnMut(&Point) -> bool>(
+        points: &mut Vec<Point>,
+        mut f: F,
+    ) -> Vec<usize> {
+        let mut indices = Vec::new();
+        let mut index = 0;
+        points.retain(|point| {
+            let keep = f(point);
+            if keep {
+                indices.push(index);
+            }
+            index += 1;
+            keep
+        });
+        indices
+    }
+
+    /// Returns the convex hull of these points' xy footprint, in counter-clockwise order.
+    ///
+    /// Uses Andrew's monotone chain algorithm. Collinear points on the hull's edges are not
+    /// included. If there are fewer than three distinct xy positions, or they are all collinear,
+    /// the distinct extreme positions are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Point;
+    /// let points: Vec<_> = [(0., 0.), (2., 0.), (1., 1.), (2., 2.), (0., 2.)]
+    ///     .iter()
+    ///     .map(|&(x, y)| Point { x, y, ..Default::default() })
+    ///     .collect();
+    /// assert_eq!(
+    ///     vec![(0., 0.), (2., 0.), (2., 2.), (0., 2.)],
+    ///     Point::convex_hull_2d(&points)
+    /// );
+    /// ```
+    pub fn convex_hull_2d(points: &[Point]) -> Vec<(f64, f64)> {
+        fn half_hull<'a, I: Iterator<Item = &'a (f64, f64)>>(xy: I) -> Vec<(f64, f64)> {
+            let mut hull: Vec<(f64, f64)> = Vec::new();
+            for &c in xy {
+                while hull.len() >= 2 {
+                    let (a, b) = (hull[hull.len() - 2], hull[hull.len() - 1]);
+                    if (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0) > 0. {
+                        break;
+                    }
+                    hull.pop();
+                }
+                hull.push(c);
+            }
+            // The last point of each half is the first point of the other half.
+            hull.pop();
+            hull
+        }
+
+        let mut xy: Vec<_> = points.iter().map(|point| (point.x, point.y)).collect();
+        xy.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        xy.dedup();
+        if xy.len() < 3 {
+            return xy;
+        }
+        let mut hull = half_hull(xy.iter());
+        hull.extend(half_hull(xy.iter().rev()));
+        hull
+    }
+
+    /// Returns the number of these points per unit of area inside their xy convex hull.
+    ///
+    /// This is more accurate than `Header::point_density` for irregular footprints. Returns zero if
+    /// the hull has no area.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Point;
+    /// let points: Vec<_> = [(0., 0.), (2., 0.), (0., 2.), (0.5, 0.5)]
+    ///     .iter()
+    ///     .map(|&(x, y)| Point { x, y, ..Default::default() })
+    ///     .collect();
+    /// assert_eq!(2., Point::density_from_hull(&points));
+    /// ```
+    pub fn density_from_hull(points: &[Point]) -> f64 {
+        let hull = Point::convex_hull_2d(points);
+        let mut area = 0.;
+        for (i, a) in hull.iter().enumerate() {
+            let b = hull[(i + 1) % hull.len()];
+            area += a.0 * b.1 - b.0 * a.1;
+        }
+        area /= 2.;
+        if area > 0. {
+            points.len() as f64 / area
+        } else {
+            0.
+        }
+    }
+
+    /// Returns each of these points' height above the ground.
+    ///
+    /// The ground surface is a grid of `cell_size` square cells holding the lowest z of the
+    /// `Ground` points that fall in each cell. Each point's height is its z minus the ground z of
+    /// the cell it falls in. Points over cells with no ground points, and all points if `cell_size`
+    /// is not positive, get `NaN`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Point;
+    /// use las::point::Classification;
+    /// let points = vec![
+    ///     Point { x: 0.5, y: 0.5, z: 10., classification: Classification::Ground, ..Default::default() },
+    ///     Point { x: 0.2, y: 0.8, z: 12.5, ..Default::default() },
+    ///     Point { x: 5., y: 5., z: 12., ..Default::default() },
+    /// ];
+    /// let heights = Point::height_above_ground(&points, 1.);
+    /// assert_eq!(0., heights[0]);
+    /// assert_eq!(2.5, heights[1]);
+    /// assert!(heights[2].is_nan());
+    /// ```
+    pub fn height_above_ground(points: &[Point], cell_size: f64) -> Vec<f64> {
+        if cell_size.is_nan() || cell_size <= 0. {
+            return vec![f64::NAN; points.len()];
+        }
+        let cell = |point: &Point| {
+            (
+                (point.x / cell_size).floor() as i64,
+                (point.y / cell_size).floor() as i64,
+            )
+        };
+        let mut ground = HashMap::new();
+        for point in points
+            .iter()
+            .filter(|point| point.classification == Classification::Ground)
+        {
+            let z = ground.entry(cell(point)).or_insert(point.z);
+            if point.z < *z {
+                *z = point.z;
+            }
+        }
+        points
+            .iter()
+            .map(|point| {
+                ground
+                    .get(&cell(point))
+                    .map_or(f64::NAN, |ground_z| point.z - ground_z)
+            })
+            .collect()
+    }
+
+    /// Counts these points' intensities into `bins` equal-width bins spanning the full 16 bit range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Point;
+    /// let points = vec![
+    ///     Point { intensity: 0, ..Default::default() },
+    ///     Point { intensity: 40000, ..Default::default() },
+    ///     Point { intensity: 65535, ..Default::default() },
+    /// ];
+    /// assert_eq!(vec![1, 2], Point::intensity_histogram(&points, 2));
+    /// ```
+    pub fn intensity_histogram(points: &[Point], bins: usize) -> Vec<u64> {
+        let mut histogram = vec![0; bins];
+        if bins > 0 {
+            for point in points {
+                histogram[usize::from(point.intensity) * bins / 65536] += 1;
+            }
+        }
+        histogram
+    }
+
+    /// Stretches these points' intensities to the full 16 bit range.
+    ///
+    /// Intensities at the `low_percentile` and `high_percentile` (each from 0 to 100) are mapped
+    /// to 0 and 65535, intensities in between are scaled linearly, and intensities outside are
+    /// clamped. If the two percentiles have the same intensity, nothing is changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Point;
+    /// let mut points: Vec<_> = (100..=200)
+    ///     .map(|intensity| Point { intensity, ..Default::default() })
+    ///     .collect();
+    /// Point::stretch_intensity(&mut points, 0., 100.);
+    /// assert_eq!(0, points[0].intensity);
+    /// assert_eq!(65535, points[100].intensity);
+    /// ```
+    pub fn stretch_intensity(points: &mut [Point], low_percentile: f64, high_percentile: f64) {
+        if points.is_empty() {
+            return;
+        }
+        let mut intensities: Vec<_> = points.iter().map(|point| point.intensity).collect();
+        intensities.sort_unstable();
+        let percentile = |p: f64| {
+            let index = (p.clamp(0., 100.) / 100. * (intensities.len() - 1) as f64).round();
+            f64::from(intensities[index as usize])
+        };
+        let low = percentile(low_percentile);
+        let high = percentile(high_percentile);
+        if high <= low {
+            return;
+        }
+        for point in points {
+            let stretched = (f64::from(point.intensity) - low) / (high - low) * 65535.;
+            point.intensity = stretched.round().clamp(0., 65535.) as u16;
+        }
+    }
+
     /// Returns true if this point matches the point format.
     ///
     /// "Matches" means that the set of optional attributes is exactly the same.
@@ -283,11 +680,99 @@ impl Point {
     /// assert!(point.matches(&format));
     /// ```
     pub fn matches(&self, format: &Format) -> bool {
-        self.gps_time.is_some() == format.has_gps_time
-            && self.color.is_some() == format.has_color
-            && self.waveform.is_some() == format.has_waveform
-            && self.nir.is_some() == format.has_nir
-            && self.extra_bytes.len() == format.extra_bytes as usize
+        self.mismatched_field(format).is_none()
+    }
+
+    /// Returns the name of the first field of this point that doesn't match the point format, if
+    /// any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::point::Format;
+    /// use las::Point;
+    /// let point = Point { gps_time: Some(42.), ..Default::default() };
+    /// assert_eq!(Some("gps_time"), point.mismatched_field(&Format::new(0).unwrap()));
+    /// assert_eq!(None, point.mismatched_field(&Format::new(1).unwrap()));
+    /// ```
+    pub fn mismatched_field(&self, format: &Format) -> Option<&'static str> {
+        if self.gps_time.is_some() != format.has_gps_time {
+            Some("gps_time")
+        } else if self.color.is_some() != format.has_color {
+            Some("color")
+        } else if self.waveform.is_some() != format.has_waveform {
+            Some("waveform")
+        } else if self.nir.is_some() != format.has_nir {
+            Some("nir")
+        } else if self.extra_bytes.len() != format.extra_bytes as usize {
+            Some("extra_bytes")
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if this point has a color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::point::Format;
+    /// use las::{Color, Point};
+    /// let point = Point { gps_time: Some(42.), color: Some(Color::new(1, 2, 3)), ..Default::default() };
+    /// assert!(point.matches(&Format::new(3).unwrap()));
+    /// assert!(point.has_color() && point.has_time());
+    /// ```
+    pub fn has_color(&self) -> bool {
+        self.color.is_some()
+    }
+
+    /// Returns true if this point has a gps time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::point::Format;
+    /// use las::Point;
+    /// let point = Point::default();
+    /// assert!(point.matches(&Format::new(0).unwrap()));
+    /// assert!(!point.has_time() && !point.has_color());
+    /// ```
+    pub fn has_time(&self) -> bool {
+        self.gps_time.is_some()
+    }
+
+    /// Returns the luminance of this point's color, or `None` if it has no color.
+    ///
+    /// See `Color::luminance`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Color, Point};
+    /// let point = Point { color: Some(Color::new(0, 0, 65535)), ..Default::default() };
+    /// assert_eq!(Some(7471), point.luminance());
+    /// assert_eq!(None, Point::default().luminance());
+    /// ```
+    pub fn luminance(&self) -> Option<u16> {
+        self.color.map(|color| color.luminance())
+    }
+}
+
+impl fmt::Display for Point {
+    /// Formats the point's coordinates, classification, intensity, and return information, e.g.
+    /// `(1, 2, 3) class=2 int=150 ret=1/3`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "({}, {}, {}) class={} int={} ret={}/{}",
+            self.x,
+            self.y,
+            self.z,
+            u8::from(self.classification),
+            self.intensity,
+            self.return_number,
+            self.number_of_returns
+        )
     }
 }
 
@@ -295,6 +780,171 @@ impl Point {
 mod tests {
     use super::*;
 
+    #[test]
+    fn set_gps_time_for_format_1() {
+        use std::io::Cursor;
+        use {Builder, Write, Writer};
+
+        let mut points = vec![Point::default(); 3];
+        assert!(points[0].matches(&Format::new(0).unwrap()));
+        let mut builder = Builder::default();
+        builder.point_format = Format::new(1).unwrap();
+        let mut writer =
+            Writer::new(Cursor::new(Vec::new()), builder.into_header().unwrap()).unwrap();
+        assert!(writer.write(points[0].clone()).is_err());
+        Point::set_gps_time_constant(&mut points, 1.5);
+        for point in points {
+            writer.write(point).unwrap();
+        }
+        assert_eq!(3, writer.header().number_of_points());
+    }
+
+    #[test]
+    fn z_slice() {
+        let points = (0..10)
+            .map(|i| Point {
+                z: f64::from(i),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let slice = Point::z_slice(&points, 2.5, 5.);
+        assert_eq!(
+            vec![3., 4., 5.],
+            slice.iter().map(|point| point.z).collect::<Vec<_>>()
+        );
+        assert!(Point::z_slice(&points, 5., 2.).is_empty());
+    }
+
+    #[test]
+    fn display() {
+        let point = Point {
+            x: 1.5,
+            y: 2.,
+            z: -3.,
+            classification: Classification::Ground,
+            intensity: 150,
+            return_number: 1,
+            number_of_returns: 3,
+            ..Default::default()
+        };
+        assert_eq!("(1.5, 2, -3) class=2 int=150 ret=1/3", point.to_string());
+    }
+
+    #[test]
+    fn retain_indexed() {
+        let mut points = (0..5)
+            .map(|i| Point {
+                x: f64::from(i),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let indices = Point::retain_indexed(&mut points, |point| point.x != 1. && point.x != 4.);
+        assert_eq!(vec![0, 2, 3], indices);
+        for (point, &index) in points.iter().zip(&indices) {
+            assert_eq!(index as f64, point.x);
+        }
+    }
+
+    #[test]
+    fn convex_hull_2d_square() {
+        let mut points = Vec::new();
+        for x in 0..5 {
+            for y in 0..5 {
+                points.push(Point {
+                    x: f64::from(x),
+                    y: f64::from(y),
+                    z: f64::from(x * y),
+                    ..Default::default()
+                });
+            }
+        }
+        assert_eq!(
+            vec![(0., 0.), (4., 0.), (4., 4.), (0., 4.)],
+            Point::convex_hull_2d(&points)
+        );
+    }
+
+    #[test]
+    fn convex_hull_2d_degenerate() {
+        let point = |x, y| Point {
+            x,
+            y,
+            ..Default::default()
+        };
+        assert!(Point::convex_hull_2d(&[]).is_empty());
+        assert_eq!(
+            vec![(1., 1.)],
+            Point::convex_hull_2d(&[point(1., 1.), point(1., 1.)])
+        );
+        assert_eq!(
+            vec![(0., 0.), (2., 2.)],
+            Point::convex_hull_2d(&[point(2., 2.), point(0., 0.), point(1., 1.)])
+        );
+    }
+
+    #[test]
+    fn remove_withheld() {
+        let mut points: Vec<_> = (0..5)
+            .map(|i| Point {
+                intensity: i,
+                is_withheld: i == 1 || i == 3,
+                is_synthetic: i == 4,
+                ..Default::default()
+            })
+            .collect();
+        Point::remove_withheld(&mut points);
+        let intensities: Vec<_> = points.iter().map(|point| point.intensity).collect();
+        assert_eq!(vec![0, 2, 4], intensities);
+        Point::remove_synthetic(&mut points);
+        assert_eq!(2, points.len());
+    }
+
+    #[test]
+    fn stretch_narrow_intensity_range() {
+        let mut points: Vec<_> = (1000..1100)
+            .map(|intensity| Point {
+                intensity,
+                ..Default::default()
+            })
+            .collect();
+        points.push(Point {
+            intensity: 60000,
+            ..Default::default()
+        });
+        Point::stretch_intensity(&mut points, 1., 99.);
+        let min = points.iter().map(|point| point.intensity).min().unwrap();
+        let max = points.iter().map(|point| point.intensity).max().unwrap();
+        assert_eq!(0, min);
+        assert_eq!(65535, max);
+        assert!(points[50].intensity > 30000 && points[50].intensity < 35000);
+    }
+
+    #[test]
+    fn stretch_constant_intensity() {
+        let mut points = vec![
+            Point {
+                intensity: 42,
+                ..Default::default()
+            };
+            3
+        ];
+        Point::stretch_intensity(&mut points, 5., 95.);
+        assert!(points.iter().all(|point| point.intensity == 42));
+    }
+
+    #[test]
+    fn intensity_histogram_bins() {
+        let points = vec![
+            Point {
+                intensity: 65535,
+                ..Default::default()
+            };
+            4
+        ];
+        assert_eq!(vec![0, 0, 0, 4], Point::intensity_histogram(&points, 4));
+        assert!(Point::intensity_histogram(&points, 0).is_empty());
+    }
+
     #[test]
     fn flags_invalid_return_number() {
         assert!(Point {
@@ -325,6 +975,23 @@ mod tests {
         .is_err());
     }
 
+    #[test]
+    fn scan_angle_degrees() {
+        use raw::point::ScanAngle;
+
+        let raw_point = raw::Point {
+            scan_angle: ScanAngle::Rank(-12),
+            ..Default::default()
+        };
+        assert_eq!(-12., Point::new(raw_point, &Default::default()).scan_angle);
+
+        let raw_point = raw::Point {
+            scan_angle: ScanAngle::Scaled(-2000),
+            ..Default::default()
+        };
+        assert_eq!(-12., Point::new(raw_point, &Default::default()).scan_angle);
+    }
+
     #[test]
     fn overlap() {
         use raw::point::Flags;
@@ -337,4 +1004,62 @@ mod tests {
         assert_eq!(Classification::Unclassified, point.classification);
         assert!(point.is_overlap);
     }
+
+    #[test]
+    fn overlap_survives_format_conversion() {
+        use raw::point::Flags;
+
+        let raw_point = raw::Point {
+            flags: Flags::TwoByte(0, 12),
+            ..Default::default()
+        };
+        let point = Point::new(raw_point, &Default::default());
+        let flags = point.flags().unwrap();
+        assert_eq!(Flags::ThreeByte(0, 8, 1), flags);
+        let raw_point = raw::Point {
+            flags: flags,
+            ..Default::default()
+        };
+        assert!(Point::new(raw_point, &Default::default()).is_overlap);
+
+        let (_, b) = flags.to_two_bytes().unwrap();
+        assert_eq!(12, b & 0b1_1111);
+    }
+
+    #[test]
+    fn height_above_ground_flat_plane() {
+        let mut points = Vec::new();
+        for x in 0..4 {
+            for y in 0..4 {
+                points.push(Point {
+                    x: f64::from(x) + 0.5,
+                    y: f64::from(y) + 0.5,
+                    z: 100.,
+                    classification: Classification::Ground,
+                    ..Default::default()
+                });
+                points.push(Point {
+                    x: f64::from(x) + 0.25,
+                    y: f64::from(y) + 0.75,
+                    z: 100. + f64::from(x + y),
+                    ..Default::default()
+                });
+            }
+        }
+        points.push(Point {
+            x: 10.,
+            y: 10.,
+            z: 105.,
+            ..Default::default()
+        });
+        let heights = Point::height_above_ground(&points, 1.);
+        assert_eq!(points.len(), heights.len());
+        for (point, height) in points.iter().zip(&heights).take(32) {
+            assert_eq!(point.z - 100., *height);
+        }
+        assert!(heights[32].is_nan());
+        assert!(Point::height_above_ground(&points, 0.)
+            .iter()
+            .all(|height| height.is_nan()));
+    }
 }
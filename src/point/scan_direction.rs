@@ -1,4 +1,13 @@
+use std::fmt;
+
 /// The direction at which the scanner mirror was traveling at the time of pulse output.
+///
+/// # Examples
+///
+/// ```
+/// use las::point::ScanDirection;
+/// assert_eq!("left to right", ScanDirection::LeftToRight.to_string());
+/// ```
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ScanDirection {
     /// The scan is moving from the right to the left.
@@ -12,3 +21,12 @@ impl Default for ScanDirection {
         ScanDirection::RightToLeft
     }
 }
+
+impl fmt::Display for ScanDirection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ScanDirection::RightToLeft => write!(f, "right to left"),
+            ScanDirection::LeftToRight => write!(f, "left to right"),
+        }
+    }
+}
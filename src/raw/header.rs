@@ -2,8 +2,8 @@
 
 use byteorder::{LittleEndian, ReadBytesExt};
 use feature::{Evlrs, LargeFiles, Waveforms};
-use raw::LASF;
-use std::io::{Read, Write};
+use raw::{Vlr, LASF};
+use std::io::{ErrorKind, Read, Write};
 use {Result, Version};
 
 /// A las header.
@@ -234,6 +234,72 @@ pub struct LargeFile {
     pub number_of_points_by_return: [u64; 15],
 }
 
+/// Computes the header size, offset to point data, and number of variable length records of a
+/// header with `padding` bytes after it, followed by vlrs of the given lengths and then
+/// `vlr_padding` bytes.
+///
+/// This is shared by `las::Header::into_raw` and `Header::recompute_offsets`.
+pub(crate) fn compute_offsets<I: IntoIterator<Item = usize>>(
+    version: Version,
+    padding: usize,
+    vlr_lens: I,
+    vlr_padding: usize,
+) -> Result<(u16, u32, u32)> {
+    use header::Error;
+
+    let header_size = usize::from(version.header_size()) + padding;
+    if header_size > usize::from(u16::MAX) {
+        return Err(Error::TooLarge(header_size).into());
+    }
+    let (n, vlr_len) = vlr_lens
+        .into_iter()
+        .fold((0, 0), |(n, acc), len| (n + 1, acc + len));
+    let offset = header_size + vlr_len + vlr_padding;
+    if offset > u32::MAX as usize {
+        return Err(Error::OffsetToPointDataTooLarge(offset).into());
+    }
+    if n > u32::MAX as usize {
+        return Err(Error::TooManyVlrs(n).into());
+    }
+    Ok((header_size as u16, offset as u32, n as u32))
+}
+
+/// How serious a `ValidationIssue` is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    /// The file can be read, but it is unusual and a strict consumer might reject it.
+    Warning,
+
+    /// The file does not conform to the las specification.
+    Error,
+}
+
+/// A problem found by `Header::validation_issues`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidationIssue {
+    /// How serious the problem is.
+    pub severity: Severity,
+
+    /// A human-readable description of the problem.
+    pub message: String,
+}
+
+impl ValidationIssue {
+    pub(crate) fn error(message: String) -> ValidationIssue {
+        ValidationIssue {
+            severity: Severity::Error,
+            message,
+        }
+    }
+
+    fn warning(message: String) -> ValidationIssue {
+        ValidationIssue {
+            severity: Severity::Warning,
+            message,
+        }
+    }
+}
+
 impl Header {
     /// Reads a raw header from a `Read`.
     ///
@@ -257,7 +323,13 @@ impl Header {
         use utils;
 
         let mut header = Header::default();
-        read.read_exact(&mut header.file_signature)?;
+        // Inputs shorter than the signature aren't las files either, so report them the same way.
+        if let Err(err) = read.read_exact(&mut header.file_signature) {
+            if err.kind() == ErrorKind::UnexpectedEof {
+                return Err(Error::FileSignature(header.file_signature).into());
+            }
+            return Err(err.into());
+        }
         if header.file_signature != LASF {
             return Err(Error::FileSignature(header.file_signature).into());
         }
@@ -317,6 +389,135 @@ impl Header {
         Ok(header)
     }
 
+    /// Sets the header size, offset to point data, and point data record length from this header's
+    /// version, padding, and point format and the given vlrs.
+    ///
+    /// This is the same computation that's done when a `las::Header` is written, so a header can
+    /// be patched in place without writing it out. `vlrs` are the (non-extended) variable length
+    /// records that will sit between the header and the point data, followed by `vlr_padding`,
+    /// and the number of variable length records is set from them too. A record length that's
+    /// longer than the point format is kept, since the extra bytes are part of the record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::raw::{Header, Vlr};
+    /// let mut header = Header::default();
+    /// header.offset_to_point_data = 0;
+    /// header.recompute_offsets(&[Vlr { data: vec![0; 10], ..Default::default() }], &[]).unwrap();
+    /// assert_eq!(227 + 54 + 10, header.offset_to_point_data);
+    /// ```
+    pub fn recompute_offsets(&mut self, vlrs: &[Vlr], vlr_padding: &[u8]) -> Result<()> {
+        use point::Format;
+
+        let (header_size, offset_to_point_data, number_of_variable_length_records) =
+            compute_offsets(
+                self.version,
+                self.padding.len(),
+                vlrs.iter().map(|vlr| 54 + vlr.data.len()),
+                vlr_padding.len(),
+            )?;
+        let format = Format::new(self.point_data_record_format)?;
+        self.header_size = header_size;
+        self.offset_to_point_data = offset_to_point_data;
+        self.number_of_variable_length_records = number_of_variable_length_records;
+        self.point_data_record_length = self.point_data_record_length.max(format.len());
+        Ok(())
+    }
+
+    /// Checks this header against the las specification, returning every problem found.
+    ///
+    /// Unlike `read_from`, which only rejects headers that can't be read at all, this is a
+    /// preflight check for files headed to strict consumers. `vlrs` are the (non-extended)
+    /// variable length records that will sit between the header and the point data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use las::raw::header::{Header, Severity};
+    /// let file = File::open("tests/data/autzen.las").unwrap();
+    /// let mut raw_header = Header::read_from(file).unwrap();
+    /// assert!(raw_header.validation_issues(&[]).is_empty());
+    /// raw_header.offset_to_point_data = 0;
+    /// let issues = raw_header.validation_issues(&[]);
+    /// assert_eq!(1, issues.len());
+    /// assert_eq!(Severity::Error, issues[0].severity);
+    /// ```
+    pub fn validation_issues(&self, vlrs: &[Vlr]) -> Vec<ValidationIssue> {
+        use point::Format;
+
+        let mut issues = Vec::new();
+
+        let vlr_len = vlrs
+            .iter()
+            .fold(0, |acc, vlr| acc + 54 + vlr.data.len() as u64);
+        let minimum_offset = u64::from(self.header_size) + vlr_len;
+        if u64::from(self.offset_to_point_data) < minimum_offset {
+            issues.push(ValidationIssue::error(format!(
+                "the offset to point data ({}) is less than the header size plus vlrs ({})",
+                self.offset_to_point_data, minimum_offset
+            )));
+        }
+
+        match Format::new(self.point_data_record_format) {
+            Ok(format) => {
+                if !self.version.supports_point_format(format) {
+                    issues.push(ValidationIssue::error(format!(
+                        "version {} does not support point format {}",
+                        self.version, format
+                    )));
+                }
+                if self.point_data_record_length < format.len() {
+                    issues.push(ValidationIssue::error(format!(
+                        "the point data record length ({}) is less than the length of format {} ({})",
+                        self.point_data_record_length,
+                        format,
+                        format.len()
+                    )));
+                }
+            }
+            Err(_) => issues.push(ValidationIssue::error(format!(
+                "unrecognized point data format: {}",
+                self.point_data_record_format
+            ))),
+        }
+
+        let number_of_points = self
+            .large_file
+            .map_or(0, |large_file| large_file.number_of_point_records)
+            .max(u64::from(self.number_of_point_records));
+        for &(axis, min, max) in &[
+            ('x', self.min_x, self.max_x),
+            ('y', self.min_y, self.max_y),
+            ('z', self.min_z, self.max_z),
+        ] {
+            if min > max {
+                let message = format!("the {} bounds are inverted: {} > {}", axis, min, max);
+                if number_of_points > 0 {
+                    issues.push(ValidationIssue::error(message));
+                } else {
+                    issues.push(ValidationIssue::warning(message));
+                }
+            }
+        }
+
+        for &(axis, scale) in &[
+            ('x', self.x_scale_factor),
+            ('y', self.y_scale_factor),
+            ('z', self.z_scale_factor),
+        ] {
+            if !(scale > 0. && scale.is_finite()) {
+                issues.push(ValidationIssue::error(format!(
+                    "the {} scale factor is not positive and finite: {}",
+                    axis, scale
+                )));
+            }
+        }
+
+        issues
+    }
+
     /// Returns the total file offset to the first byte *after* all of the points.
     ///
     /// # Examples
@@ -326,8 +527,14 @@ impl Header {
     /// assert_eq!(227, Header::default().offset_to_end_of_points());
     /// ```
     pub fn offset_to_end_of_points(&self) -> u64 {
+        let number_of_point_records = match self.large_file {
+            Some(large_file) if large_file.number_of_point_records > 0 => {
+                large_file.number_of_point_records
+            }
+            _ => u64::from(self.number_of_point_records),
+        };
         u64::from(self.offset_to_point_data)
-            + u64::from(self.number_of_point_records) * u64::from(self.point_data_record_length)
+            + number_of_point_records * u64::from(self.point_data_record_length)
     }
 
     /// Writes a raw header to a `Write`.
@@ -494,6 +701,128 @@ mod tests {
         assert!(write_read(header).is_err());
     }
 
+    #[test]
+    fn validate_broken_header() {
+        let header = Header {
+            version: Version::new(1, 2),
+            offset_to_point_data: 100,
+            point_data_record_format: 6,
+            point_data_record_length: 20,
+            number_of_point_records: 1,
+            min_x: 1.,
+            max_x: 0.,
+            x_scale_factor: 0.01,
+            y_scale_factor: 0.01,
+            z_scale_factor: -0.01,
+            ..Default::default()
+        };
+        let issues = header.validation_issues(&[]);
+        let messages = issues
+            .iter()
+            .map(|issue| issue.message.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(5, issues.len(), "{:?}", messages);
+        assert!(issues.iter().all(|issue| issue.severity == Severity::Error));
+        assert!(messages[0].contains("offset to point data"));
+        assert!(messages[1].contains("does not support point format"));
+        assert!(messages[2].contains("record length"));
+        assert!(messages[3].contains("x bounds are inverted"));
+        assert!(messages[4].contains("z scale factor"));
+    }
+
+    #[test]
+    fn validate_counts_vlrs() {
+        let mut header = Header {
+            x_scale_factor: 0.01,
+            y_scale_factor: 0.01,
+            z_scale_factor: 0.01,
+            ..Default::default()
+        };
+        let vlr = Vlr {
+            data: vec![0; 10],
+            ..Default::default()
+        };
+        assert_eq!(1, header.validation_issues(&[vlr.clone()]).len());
+        header.offset_to_point_data += 64;
+        assert!(header.validation_issues(&[vlr]).is_empty());
+    }
+
+    #[test]
+    fn recompute_offsets_matches_written_header() {
+        use {Builder, Vlr as LasVlr};
+
+        let mut builder = Builder::from((1, 4));
+        builder.padding = vec![0; 3];
+        builder.point_format.extra_bytes = 2;
+        builder.vlrs.push(LasVlr {
+            data: vec![0; 42],
+            ..Default::default()
+        });
+        let header = builder.into_header().unwrap();
+        let written = header.to_bytes().unwrap();
+        let vlrs = header
+            .vlrs()
+            .iter()
+            .map(|vlr| vlr.clone().into_raw(false).unwrap())
+            .collect::<Vec<_>>();
+        let mut raw_header = header.into_raw().unwrap();
+        let expected = raw_header.clone();
+        raw_header.header_size = 0;
+        raw_header.offset_to_point_data = 0;
+        raw_header.number_of_variable_length_records = 0;
+        raw_header.recompute_offsets(&vlrs, &[]).unwrap();
+        assert_eq!(expected, raw_header);
+        assert_eq!(written.len(), raw_header.offset_to_point_data as usize);
+    }
+
+    #[test]
+    fn recompute_offsets_with_vlr_padding() {
+        use {Builder, Vlr as LasVlr};
+
+        let mut builder = Builder::from((1, 2));
+        builder.vlrs.push(LasVlr {
+            data: vec![0; 10],
+            ..Default::default()
+        });
+        builder.vlr_padding = b"vendor padding".to_vec();
+        let header = builder.into_header().unwrap();
+        let written = header.to_bytes().unwrap();
+        let vlrs = header
+            .vlrs()
+            .iter()
+            .map(|vlr| vlr.clone().into_raw(false).unwrap())
+            .collect::<Vec<_>>();
+        let vlr_padding = header.vlr_padding().clone();
+        let mut raw_header = header.into_raw().unwrap();
+        let expected = raw_header.clone();
+        raw_header.offset_to_point_data = 0;
+        raw_header.recompute_offsets(&vlrs, &vlr_padding).unwrap();
+        assert_eq!(expected, raw_header);
+        assert_eq!(written.len(), raw_header.offset_to_point_data as usize);
+    }
+
+    #[test]
+    fn validate_inverted_bounds_without_points() {
+        let header = Header {
+            min_z: 1.,
+            x_scale_factor: 0.01,
+            y_scale_factor: 0.01,
+            z_scale_factor: 0.01,
+            ..Default::default()
+        };
+        let issues = header.validation_issues(&[]);
+        assert_eq!(1, issues.len());
+        assert_eq!(Severity::Warning, issues[0].severity);
+    }
+
+    #[test]
+    fn short_file_signature() {
+        match Header::read_from(Cursor::new(b"LA".to_vec())) {
+            Err(::Error::Header(::header::Error::FileSignature(_))) => {}
+            other => panic!("expected a file signature error, got {:?}", other),
+        }
+    }
+
     macro_rules! roundtrip {
         ($name:ident, $minor:expr) => {
             mod $name {
@@ -420,19 +420,24 @@ impl Point {
         use byteorder::{LittleEndian, WriteBytesExt};
         assert_eq!(format.extra_bytes as usize, self.extra_bytes.len());
 
+        // Pack the flags first so a value that doesn't fit fails before anything is written.
+        let two_byte_flags = if format.is_extended {
+            None
+        } else {
+            Some(self.flags.to_two_bytes()?)
+        };
         write.write_i32::<LittleEndian>(self.x)?;
         write.write_i32::<LittleEndian>(self.y)?;
         write.write_i32::<LittleEndian>(self.z)?;
         write.write_u16::<LittleEndian>(self.intensity)?;
-        if format.is_extended {
-            let (a, b, c) = self.flags.into();
+        if let Some((a, b)) = two_byte_flags {
             write.write_u8(a)?;
             write.write_u8(b)?;
-            write.write_u8(c)?;
         } else {
-            let (a, b) = self.flags.to_two_bytes()?;
+            let (a, b, c) = self.flags.into();
             write.write_u8(a)?;
             write.write_u8(b)?;
+            write.write_u8(c)?;
         }
         if format.is_extended {
             write.write_i16::<LittleEndian>(self.scan_angle.into())?;
@@ -604,7 +609,7 @@ impl Flags {
     /// ```
     pub fn is_overlap(&self) -> bool {
         match *self {
-            Flags::TwoByte(_, b) => b & 0b1111 == OVERLAP_CLASSIFICATION_CODE,
+            Flags::TwoByte(_, b) => b & 0b1_1111 == OVERLAP_CLASSIFICATION_CODE,
             Flags::ThreeByte(_, b, _) => b & 8 == 8,
         }
     }
@@ -941,6 +946,8 @@ mod tests {
     fn is_overlap() {
         assert!(!Flags::TwoByte(0, 0).is_overlap());
         assert!(Flags::TwoByte(0, OVERLAP_CLASSIFICATION_CODE).is_overlap());
+        assert!(!Flags::TwoByte(0, OVERLAP_CLASSIFICATION_CODE + 16).is_overlap());
+        assert!(Flags::TwoByte(0, OVERLAP_CLASSIFICATION_CODE + 0b1110_0000).is_overlap());
         assert!(!Flags::ThreeByte(0, 0, 0).is_overlap());
         assert!(Flags::ThreeByte(0, 8, 0).is_overlap());
         assert_eq!(
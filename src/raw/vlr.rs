@@ -4,7 +4,7 @@ use std::io::{Read, Write};
 use Result;
 
 /// A raw variable length record.
-#[derive(Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct Vlr {
     /// This value must be set to zero
     pub reserved: u16,
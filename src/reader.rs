@@ -42,15 +42,20 @@
 //! ```
 
 use std::fs::File;
-use std::io::{BufReader, Seek, SeekFrom};
+use std::io::{BufReader, ErrorKind, Seek, SeekFrom};
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 #[cfg(feature = "laz")]
 use compression::CompressedPointReader;
 
+use point::Classification;
 use std::fmt::Debug;
 use thiserror::Error;
-use {raw, Builder, Header, Point, Result, Vlr};
+use {raw, Builder, Color, Header, Point, Result, Vlr};
+
+/// Bulk reads don't preallocate room for more than this many points.
+const MAX_PREALLOCATED_POINTS: u64 = 1 << 20;
 
 /// Error while reading.
 #[derive(Error, Clone, Copy, Debug)]
@@ -62,6 +67,312 @@ pub enum Error {
     /// The offset to the start of the evlrs is too small.
     #[error("offset to the start of the evlrs is too small: {0}")]
     OffsetToEvlrsTooSmall(u64),
+
+    /// The data ran out before the number of points declared in the header were read.
+    #[error("the header declares {declared} points, but only {actual} could be read")]
+    #[allow(missing_docs)]
+    PointCountMismatch { declared: u64, actual: u64 },
+
+    /// The file is shorter than the points declared in its header.
+    #[error("the file should be at least {expected} bytes long, but it is only {actual} bytes")]
+    #[allow(missing_docs)]
+    TruncatedFile { expected: u64, actual: u64 },
+
+    /// The points are compressed, so they aren't at fixed byte offsets.
+    #[error("the points are compressed, so they aren't at fixed byte offsets")]
+    Compressed,
+
+    /// The byte offset isn't the start of a point record.
+    #[error("the byte offset {0} isn't the start of a point record")]
+    PointOffset(u64),
+
+    /// The read was cancelled.
+    #[error("the read was cancelled")]
+    Cancelled,
+}
+
+/// The most trailing bytes after the points that are kept as point padding.
+const MAX_TRAILING_POINT_PADDING: u64 = 1 << 20;
+
+/// Reads a header and its vlrs and evlrs, leaving `read` positioned at the start of the points.
+///
+/// If `read_trailing` is set and there are no evlrs, up to `MAX_TRAILING_POINT_PADDING` bytes after
+/// uncompressed points are read as point padding.
+pub(crate) fn read_header_and_vlrs<R: std::io::Read + Seek>(
+    mut read: R,
+    read_trailing: bool,
+) -> Result<Header> {
+    use std::io::Read;
+
+    let raw_header = raw::Header::read_from(&mut read)?;
+    let position = u64::from(raw_header.header_size);
+    let number_of_variable_length_records = raw_header.number_of_variable_length_records;
+    let offset_to_point_data = u64::from(raw_header.offset_to_point_data);
+    let offset_to_end_of_points = raw_header.offset_to_end_of_points();
+    let evlr = raw_header.evlr;
+
+    let mut builder = Builder::new(raw_header)?;
+
+    if position > offset_to_point_data {
+        return Err(Error::OffsetToPointDataTooSmall(offset_to_point_data as u32).into());
+    }
+    let mut vlr_bytes = Vec::new();
+    read.by_ref()
+        .take(offset_to_point_data - position)
+        .read_to_end(&mut vlr_bytes)?;
+    let mut start = 0;
+    for i in 0..number_of_variable_length_records {
+        let more_follow = i + 1 < number_of_variable_length_records;
+        match read_vlr_from(&vlr_bytes[start..], more_follow)? {
+            Some((vlr, len)) => {
+                start += len;
+                builder.vlrs.push(vlr);
+            }
+            None => {
+                warn!(
+                    "only {} of {} vlrs fit before the point data",
+                    i, number_of_variable_length_records
+                );
+                break;
+            }
+        }
+    }
+    builder.vlr_padding = vlr_bytes.split_off(start);
+
+    read.seek(SeekFrom::Start(offset_to_end_of_points))?;
+    if let Some(evlr) = evlr {
+        if evlr.start_of_first_evlr < offset_to_end_of_points {
+            return Err(Error::OffsetToEvlrsTooSmall(evlr.start_of_first_evlr).into());
+        } else if evlr.start_of_first_evlr > offset_to_end_of_points {
+            let n = evlr.start_of_first_evlr - offset_to_end_of_points;
+            read.by_ref()
+                .take(n)
+                .read_to_end(&mut builder.point_padding)?;
+        }
+        builder
+            .evlrs
+            .push(raw::Vlr::read_from(&mut read, true).map(Vlr::new)?);
+    } else if read_trailing && !builder.point_format.is_compressed {
+        let end = read.seek(SeekFrom::End(0))?;
+        let n = end.saturating_sub(offset_to_end_of_points);
+        if n > MAX_TRAILING_POINT_PADDING {
+            warn!(
+                "ignoring {} bytes after the points, more than the {} kept as point padding",
+                n, MAX_TRAILING_POINT_PADDING
+            );
+        } else if n > 0 {
+            read.seek(SeekFrom::Start(offset_to_end_of_points))?;
+            read.read_to_end(&mut builder.point_padding)?;
+        }
+    }
+
+    read.seek(SeekFrom::Start(offset_to_point_data))?;
+
+    builder.into_header()
+}
+
+/// Reads a vlr from the start of `bytes`, which hold the rest of the vlrs before the point data.
+///
+/// Some writers declare a vlr's length incorrectly. If the declared length would run past the end
+/// of `bytes`, the vlr's data is cut off there. If more vlrs should follow and the bytes where
+/// this vlr's declared data ends can't be a vlr header, but there is a vlr header inside the data,
+/// the data is cut off at that header so the following vlrs can still be read. Either way a
+/// warning is logged. Returns the vlr and the number of bytes it used, or `None` if there isn't
+/// room for a vlr header.
+fn read_vlr_from(bytes: &[u8], more_follow: bool) -> Result<Option<(Vlr, usize)>> {
+    use byteorder::{ByteOrder, LittleEndian};
+
+    if bytes.len() < VLR_HEADER_LEN {
+        return Ok(None);
+    }
+    let declared = usize::from(LittleEndian::read_u16(&bytes[20..22]));
+    let mut len = declared.min(bytes.len() - VLR_HEADER_LEN);
+    if len < declared {
+        warn!(
+            "vlr declares {} bytes of data, but only {} fit before the point data",
+            declared, len
+        );
+    }
+    if more_follow && !may_be_vlr_header(&bytes[VLR_HEADER_LEN + len..]) {
+        if let Some(n) = (0..len).find(|&n| is_vlr_header(&bytes[VLR_HEADER_LEN + n..])) {
+            warn!(
+                "vlr declares {} bytes of data, but the next vlr starts after {} bytes",
+                declared, n
+            );
+            len = n;
+        }
+    }
+    let mut record = bytes[..VLR_HEADER_LEN + len].to_vec();
+    LittleEndian::write_u16(&mut record[20..22], len as u16);
+    raw::Vlr::read_from(&record[..], false).map(|vlr| Some((Vlr::new(vlr), VLR_HEADER_LEN + len)))
+}
+
+const VLR_HEADER_LEN: usize = 54;
+
+/// Returns whether `bytes` could start with a vlr header, i.e. there's room for one, its reserved
+/// field is zero or 0xAABB, and its user id, which may be empty, is printable up to the first nul.
+fn may_be_vlr_header(bytes: &[u8]) -> bool {
+    if bytes.len() < VLR_HEADER_LEN || !(bytes[0..2] == [0, 0] || bytes[0..2] == [0xBB, 0xAA]) {
+        return false;
+    }
+    bytes[2..18]
+        .iter()
+        .take_while(|&&b| b != 0)
+        .all(|b| (0x20..0x7f).contains(b))
+}
+
+/// Returns whether `bytes` clearly start with a vlr header, i.e. one that `may_be_vlr_header`
+/// accepts with a non-empty, nul-padded user id.
+fn is_vlr_header(bytes: &[u8]) -> bool {
+    if !may_be_vlr_header(bytes) {
+        return false;
+    }
+    let user_id = &bytes[2..18];
+    let end = user_id
+        .iter()
+        .position(|&b| b == 0)
+        .unwrap_or(user_id.len());
+    end > 0 && user_id[end..].iter().all(|&b| b == 0)
+}
+
+/// Decodes one point record from bytes, using a header's point format and transforms.
+///
+/// Any bytes after the record are ignored. Returns an error if there aren't enough bytes for a
+/// record.
+///
+/// # Examples
+///
+/// ```
+/// use las::{Header, Point};
+/// use las::reader::decode_point;
+/// let header = Header::default();
+/// let bytes = vec![0; header.point_format().len() as usize];
+/// assert_eq!(Point::default(), decode_point(&bytes, &header).unwrap());
+/// assert!(decode_point(&bytes[1..], &header).is_err());
+/// ```
+pub fn decode_point(bytes: &[u8], header: &Header) -> Result<Point> {
+    read_point_from(&mut &bytes[..], header)
+}
+
+/// The optional fields decoded by a `Reader`, see `Reader::with_fields`.
+///
+/// Coordinates are always decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FieldMask {
+    /// Decode the intensity.
+    pub intensity: bool,
+
+    /// Decode the classification, along with the return numbers and the other flags that share
+    /// its bytes.
+    pub classification: bool,
+
+    /// Decode the gps time.
+    pub gps_time: bool,
+
+    /// Decode the color.
+    pub color: bool,
+}
+
+impl FieldMask {
+    /// Returns a mask that decodes every field, including the ones that can't be selected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::reader::FieldMask;
+    /// assert!(FieldMask::all().gps_time);
+    /// ```
+    pub fn all() -> FieldMask {
+        FieldMask {
+            intensity: true,
+            classification: true,
+            gps_time: true,
+            color: true,
+        }
+    }
+
+    /// Returns a mask that only decodes coordinates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::reader::FieldMask;
+    /// assert!(!FieldMask::coordinates().gps_time);
+    /// ```
+    pub fn coordinates() -> FieldMask {
+        FieldMask {
+            intensity: false,
+            classification: false,
+            gps_time: false,
+            color: false,
+        }
+    }
+}
+
+/// Decodes the coordinates and the selected fields of one point record.
+///
+/// Fields that aren't selected are left at their defaults, i.e. `None` for gps time and color.
+/// The scan angle, user data, point source id, waveform, nir, and extra bytes are only decoded
+/// with `FieldMask::all`, which is the same as `decode_point`.
+///
+/// # Examples
+///
+/// ```
+/// use las::{Header, Point};
+/// use las::reader::{decode_point_fields, FieldMask};
+/// let header = Header::default();
+/// let bytes = vec![0; header.point_format().len() as usize];
+/// let point = decode_point_fields(&bytes, &header, FieldMask::coordinates()).unwrap();
+/// assert_eq!(Point::default(), point);
+/// ```
+pub fn decode_point_fields(bytes: &[u8], header: &Header, fields: FieldMask) -> Result<Point> {
+    use byteorder::{ByteOrder, LittleEndian};
+    use raw::point::Flags;
+
+    if fields == FieldMask::all() {
+        return decode_point(bytes, header);
+    }
+    let format = header.point_format();
+    if bytes.len() < usize::from(format.len()) {
+        return Err(std::io::Error::new(
+            ErrorKind::UnexpectedEof,
+            "not enough bytes for a point record",
+        )
+        .into());
+    }
+    let mut raw_point = raw::Point {
+        x: LittleEndian::read_i32(&bytes[0..4]),
+        y: LittleEndian::read_i32(&bytes[4..8]),
+        z: LittleEndian::read_i32(&bytes[8..12]),
+        ..Default::default()
+    };
+    if fields.intensity {
+        raw_point.intensity = LittleEndian::read_u16(&bytes[12..14]);
+    }
+    if fields.classification {
+        raw_point.flags = if format.is_extended {
+            Flags::ThreeByte(bytes[14], bytes[15], bytes[16])
+        } else {
+            Flags::TwoByte(bytes[14], bytes[15])
+        };
+    }
+    let gps_time_offset = if format.is_extended { 22 } else { 20 };
+    if fields.gps_time && format.has_gps_time {
+        raw_point.gps_time = Some(LittleEndian::read_f64(&bytes[gps_time_offset..]));
+    }
+    if fields.color && format.has_color {
+        let offset = if format.has_gps_time {
+            gps_time_offset + 8
+        } else {
+            gps_time_offset
+        };
+        raw_point.color = Some(Color::new(
+            LittleEndian::read_u16(&bytes[offset..]),
+            LittleEndian::read_u16(&bytes[offset + 2..]),
+            LittleEndian::read_u16(&bytes[offset + 4..]),
+        ));
+    }
+    Ok(Point::new(raw_point, header.transforms()))
 }
 
 #[inline]
@@ -74,11 +385,78 @@ pub(crate) fn read_point_from<R: std::io::Read>(
     point
 }
 
+/// A point that could not be read by `Reader::read_all_lenient`.
+#[derive(Debug)]
+pub struct ReadWarning {
+    /// The index of the point that could not be read.
+    pub index: u64,
+
+    /// The error that occurred while reading the point.
+    pub error: ::Error,
+}
+
+/// The points flagged by one of the checks of `Reader::validate_all`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IssueSummary {
+    /// The number of points flagged.
+    pub count: u64,
+
+    /// The indices of the first few flagged points, at most `MAX_EXAMPLES` of them.
+    pub examples: Vec<u64>,
+}
+
+impl IssueSummary {
+    /// The maximum number of example indices kept for each issue.
+    pub const MAX_EXAMPLES: usize = 10;
+
+    fn add(&mut self, index: u64) {
+        self.count += 1;
+        if self.examples.len() < IssueSummary::MAX_EXAMPLES {
+            self.examples.push(index);
+        }
+    }
+}
+
+/// The result of checking every point in a file, created by `Reader::validate_all`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ValidationReport {
+    /// The number of points checked.
+    pub points_checked: u64,
+
+    /// Points whose return numbers are inconsistent, see `Header::validate_returns`.
+    pub returns: IssueSummary,
+
+    /// Points with a reserved classification.
+    pub classification: IssueSummary,
+
+    /// Points whose scaled coordinates are not finite.
+    pub coordinates: IssueSummary,
+}
+
+impl ValidationReport {
+    /// Returns true if no point was flagged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::reader::ValidationReport;
+    /// assert!(ValidationReport::default().is_valid());
+    /// ```
+    pub fn is_valid(&self) -> bool {
+        self.returns.count == 0 && self.classification.count == 0 && self.coordinates.count == 0
+    }
+}
+
 /// Trait to specify behaviour a a PointReader
 pub(crate) trait PointReader: Debug {
     fn read_next(&mut self) -> Option<Result<Point>>;
+    fn read_next_raw(&mut self) -> Option<Result<&[u8]>>;
+    fn set_fields(&mut self, fields: FieldMask);
     fn seek(&mut self, position: u64) -> Result<()>;
     fn header(&self) -> &Header;
+    fn points_read(&self) -> u64;
+    /// Returns `None` if the points don't have fixed byte offsets, e.g. when they're compressed.
+    fn offset_to_point_data(&self) -> Option<u64>;
 }
 
 /// An iterator over of the points in a `Reader`.
@@ -104,17 +482,52 @@ struct UncompressedPointReader<R: std::io::Read + Seek> {
     offset_to_point_data: u64,
     /// index of the last point read
     last_point_idx: u64,
+    buffer: Vec<u8>,
+    fields: FieldMask,
 }
 
-impl<R: std::io::Read + Seek + Debug> PointReader for UncompressedPointReader<R> {
-    fn read_next(&mut self) -> Option<Result<Point>> {
+impl<R: std::io::Read + Seek + Debug> UncompressedPointReader<R> {
+    fn read_record(&mut self) -> Option<Result<()>> {
         if self.last_point_idx < self.header.number_of_points() {
             self.last_point_idx += 1;
-            Some(read_point_from(&mut self.source, &self.header))
+            // Reading whole records keeps the source aligned even if a record can't be decoded.
+            self.buffer
+                .resize(usize::from(self.header.point_format().len()), 0);
+            match self.source.read_exact(&mut self.buffer) {
+                Ok(()) => Some(Ok(())),
+                Err(ref err) if err.kind() == ErrorKind::UnexpectedEof => {
+                    Some(Err(Error::PointCountMismatch {
+                        declared: self.header.number_of_points(),
+                        actual: self.last_point_idx - 1,
+                    }
+                    .into()))
+                }
+                Err(err) => Some(Err(err.into())),
+            }
         } else {
             None
         }
     }
+}
+
+impl<R: std::io::Read + Seek + Debug> PointReader for UncompressedPointReader<R> {
+    fn read_next(&mut self) -> Option<Result<Point>> {
+        self.read_record().map(|result| {
+            result.and_then(|()| decode_point_fields(&self.buffer, &self.header, self.fields))
+        })
+    }
+
+    fn read_next_raw(&mut self) -> Option<Result<&[u8]>> {
+        match self.read_record() {
+            Some(Ok(())) => Some(Ok(&self.buffer)),
+            Some(Err(err)) => Some(Err(err)),
+            None => None,
+        }
+    }
+
+    fn set_fields(&mut self, fields: FieldMask) {
+        self.fields = fields;
+    }
 
     fn seek(&mut self, position: u64) -> Result<()> {
         self.last_point_idx = position;
@@ -127,6 +540,14 @@ impl<R: std::io::Read + Seek + Debug> PointReader for UncompressedPointReader<R>
     fn header(&self) -> &Header {
         &self.header
     }
+
+    fn points_read(&self) -> u64 {
+        self.last_point_idx
+    }
+
+    fn offset_to_point_data(&self) -> Option<u64> {
+        Some(self.offset_to_point_data)
+    }
 }
 
 /// A trait for objects which read LAS data.
@@ -194,6 +615,9 @@ impl Reader {
     /// This does *not* wrap the `Read` in a `BufRead`, so if you're concered about performance you
     /// should do that wrapping yourself (or use `from_path`).
     ///
+    /// Returns `Error::TruncatedFile` if an uncompressed source is too short to hold all of the
+    /// points declared in the header.
+    ///
     /// # Examples
     ///
     /// ```
@@ -204,53 +628,80 @@ impl Reader {
     /// let reader = Reader::new(BufReader::new(file)).unwrap();
     /// ```
     pub fn new<R: std::io::Read + Seek + Debug + 'static>(mut read: R) -> Result<Reader> {
-        use std::io::Read;
+        let header = read_header_and_vlrs(&mut read, true)?;
+        Reader::from_header_and_points(header, read)
+    }
 
-        let raw_header = raw::Header::read_from(&mut read)?;
-        let mut position = u64::from(raw_header.header_size);
-        let number_of_variable_length_records = raw_header.number_of_variable_length_records;
-        let offset_to_point_data = u64::from(raw_header.offset_to_point_data);
-        let offset_to_end_of_points = raw_header.offset_to_end_of_points();
-        let evlr = raw_header.evlr;
+    /// Creates a new reader that doesn't check up front that the source holds all of its points.
+    ///
+    /// Use this with `read_all_lenient` to salvage the points of a truncated file, which
+    /// `Reader::new` would reject with `Error::TruncatedFile`. Reading past the end of the data
+    /// returns `Error::PointCountMismatch` instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use las::{Read, Reader};
+    /// let mut bytes = std::fs::read("tests/data/autzen.las").unwrap();
+    /// let len = bytes.len() - 100;
+    /// bytes.truncate(len);
+    /// assert!(Reader::new(Cursor::new(bytes.clone())).is_err());
+    /// let mut reader = Reader::new_lenient(Cursor::new(bytes)).unwrap();
+    /// let (points, warnings) = reader.read_all_lenient();
+    /// assert_eq!(1, warnings.len());
+    /// ```
+    pub fn new_lenient<R: std::io::Read + Seek + Debug + 'static>(mut read: R) -> Result<Reader> {
+        let header = read_header_and_vlrs(&mut read, true)?;
+        Reader::with_point_source(header, read, false)
+    }
 
-        let mut builder = Builder::new(raw_header)?;
+    /// Creates a reader over point data with a header that was read separately.
+    ///
+    /// The points are read starting at the current position of `read`, so the header doesn't have
+    /// to be parsed again, e.g. when the header is fetched once and shared between workers that
+    /// each open the point data. Compressed point data refers to its chunk table by file offset,
+    /// so for compressed data `read` must be the whole file, positioned at the start of the
+    /// points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use las::{Header, Read, Reader};
+    /// let bytes = std::fs::read("tests/data/autzen.las").unwrap();
+    /// let header = Header::from_bytes(&bytes).unwrap();
+    /// let points = bytes[header.to_bytes().unwrap().len()..].to_vec();
+    /// let mut reader = Reader::from_header_and_points(header, Cursor::new(points)).unwrap();
+    /// assert_eq!(106, reader.points().count());
+    /// ```
+    pub fn from_header_and_points<R: std::io::Read + Seek + Debug + 'static>(
+        header: Header,
+        read: R,
+    ) -> Result<Reader> {
+        Reader::with_point_source(header, read, true)
+    }
 
-        if !cfg!(feature = "laz") && builder.point_format.is_compressed {
+    fn with_point_source<R: std::io::Read + Seek + Debug + 'static>(
+        header: Header,
+        mut read: R,
+        check_length: bool,
+    ) -> Result<Reader> {
+        if !cfg!(feature = "laz") && header.point_format().is_compressed {
             return Err(::Error::Laszip);
         }
-
-        for _ in 0..number_of_variable_length_records {
-            let vlr = raw::Vlr::read_from(&mut read, false).map(Vlr::new)?;
-            position += vlr.len(false) as u64;
-            builder.vlrs.push(vlr);
-        }
-        if position > offset_to_point_data {
-            return Err(Error::OffsetToPointDataTooSmall(offset_to_point_data as u32).into());
-        } else if position < offset_to_point_data {
-            read.by_ref()
-                .take(offset_to_point_data - position)
-                .read_to_end(&mut builder.vlr_padding)?;
-        }
-
-        read.seek(SeekFrom::Start(offset_to_end_of_points))?;
-        if let Some(evlr) = evlr {
-            if evlr.start_of_first_evlr < offset_to_end_of_points {
-                return Err(Error::OffsetToEvlrsTooSmall(evlr.start_of_first_evlr).into());
-            } else if evlr.start_of_first_evlr > offset_to_end_of_points {
-                let n = evlr.start_of_first_evlr - offset_to_end_of_points;
-                read.by_ref()
-                    .take(n)
-                    .read_to_end(&mut builder.point_padding)?;
+        let offset_to_point_data = read.stream_position()?;
+        if check_length && !header.point_format().is_compressed {
+            // Catch truncated files before reading points, rather than partway through them.
+            let expected = offset_to_point_data
+                + header.number_of_points() * u64::from(header.point_format().len());
+            let actual = read.seek(SeekFrom::End(0))?;
+            if actual < expected {
+                return Err(Error::TruncatedFile { expected, actual }.into());
             }
-            builder
-                .evlrs
-                .push(raw::Vlr::read_from(&mut read, true).map(Vlr::new)?);
+            read.seek(SeekFrom::Start(offset_to_point_data))?;
         }
 
-        read.seek(SeekFrom::Start(offset_to_point_data))?;
-
-        let header = builder.into_header()?;
-
         #[cfg(feature = "laz")]
         {
             if header.point_format().is_compressed {
@@ -264,6 +715,8 @@ impl Reader {
                         header,
                         offset_to_point_data,
                         last_point_idx: 0,
+                        buffer: Vec::new(),
+                        fields: FieldMask::all(),
                     }),
                 })
             }
@@ -276,10 +729,332 @@ impl Reader {
                     header,
                     offset_to_point_data,
                     last_point_idx: 0,
+                    buffer: Vec::new(),
+                    fields: FieldMask::all(),
                 }),
             })
         }
     }
+
+    /// Reads all remaining points, skipping over the ones that can't be read.
+    ///
+    /// Instead of aborting on the first error, each point that can't be read is recorded as a
+    /// `ReadWarning` and reading continues with the next point. If the data runs out before all of
+    /// the points declared in the header have been read, the points that were read are returned
+    /// along with a single warning. `Reader::new` rejects truncated uncompressed files, so open
+    /// them with `Reader::new_lenient` to salvage their points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Read, Reader};
+    /// let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    /// let (points, warnings) = reader.read_all_lenient();
+    /// assert_eq!(reader.header().number_of_points(), points.len() as u64);
+    /// assert!(warnings.is_empty());
+    /// ```
+    pub fn read_all_lenient(&mut self) -> (Vec<Point>, Vec<ReadWarning>) {
+        let mut points = self.point_buffer();
+        let mut warnings = Vec::new();
+        loop {
+            let index = self.points_read();
+            match self.point_reader.read_next() {
+                Some(Ok(point)) => points.push(point),
+                Some(Err(error)) => {
+                    warn!("could not read point {}: {}", index, error);
+                    let is_truncated =
+                        matches!(error, ::Error::Reader(Error::PointCountMismatch { .. }));
+                    warnings.push(ReadWarning { index, error });
+                    if is_truncated {
+                        break;
+                    }
+                }
+                None => break,
+            }
+        }
+        (points, warnings)
+    }
+
+    /// Reads all remaining points and reports the ones that are malformed.
+    ///
+    /// Every point is checked for inconsistent return numbers, reserved classifications, and
+    /// coordinates that aren't finite after scaling, without stopping at the first bad point.
+    /// Returns an error if a point can't be read at all.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Reader;
+    /// let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    /// let report = reader.validate_all().unwrap();
+    /// assert_eq!(106, report.points_checked);
+    /// assert!(report.is_valid());
+    /// ```
+    pub fn validate_all(&mut self) -> Result<ValidationReport> {
+        let mut report = ValidationReport::default();
+        loop {
+            let index = self.points_read();
+            let point = match self.point_reader.read_next() {
+                Some(point) => point?,
+                None => return Ok(report),
+            };
+            report.points_checked += 1;
+            if self.header().has_inconsistent_returns(&point) {
+                report.returns.add(index);
+            }
+            if let Classification::Reserved(_) = point.classification {
+                report.classification.add(index);
+            }
+            if !(point.x.is_finite() && point.y.is_finite() && point.z.is_finite()) {
+                report.coordinates.add(index);
+            }
+        }
+    }
+
+    /// Reads all remaining points, stopping early if `cancel` is set.
+    ///
+    /// The flag is checked before each point is read, so a read can be cancelled from another
+    /// thread, e.g. when a user closes a window while a large file is loading. A cancelled read
+    /// returns `Error::Cancelled` and the points read so far are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::AtomicBool;
+    /// use las::{Read, Reader};
+    /// let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    /// let points = reader.read_all_cancellable(&AtomicBool::new(false)).unwrap();
+    /// assert_eq!(reader.header().number_of_points(), points.len() as u64);
+    /// assert!(reader.read_all_cancellable(&AtomicBool::new(true)).is_err());
+    /// ```
+    pub fn read_all_cancellable(&mut self, cancel: &AtomicBool) -> Result<Vec<Point>> {
+        let mut points = self.point_buffer();
+        loop {
+            if cancel.load(Ordering::Relaxed) {
+                return Err(Error::Cancelled.into());
+            }
+            match self.point_reader.read_next() {
+                Some(point) => points.push(point?),
+                None => return Ok(points),
+            }
+        }
+    }
+
+    /// Reads every `step`th remaining point, seeking past the points in between.
+    ///
+    /// This is a cheap way to preview a large file: the skipped points are never read or decoded.
+    /// The first point read is the next point, so a step of two from the start of a file reads the
+    /// even-indexed points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Reader;
+    /// let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    /// let points = reader.read_decimated(10).unwrap();
+    /// assert_eq!(11, points.len());
+    /// ```
+    pub fn read_decimated(&mut self, step: u64) -> Result<Vec<Point>> {
+        assert!(step > 0, "the decimation step must be positive");
+        let number_of_points = self.header().number_of_points();
+        let mut points = Vec::with_capacity(
+            (self.points_remaining() / step + 1).min(MAX_PREALLOCATED_POINTS) as usize,
+        );
+        loop {
+            match self.point_reader.read_next() {
+                Some(point) => points.push(point?),
+                None => return Ok(points),
+            }
+            let next = match self.points_read().checked_add(step - 1) {
+                Some(next) if next < number_of_points => next,
+                _ => return Ok(points),
+            };
+            if step > 1 {
+                self.point_reader.seek(next)?;
+            }
+        }
+    }
+
+    /// Skips over the next `n` points without reading them.
+    ///
+    /// This is a single seek, so it's cheap for uncompressed data. The number of points read
+    /// advances by `n`, as if the points had been read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Read, Reader};
+    /// let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    /// reader.skip_points(10).unwrap();
+    /// assert_eq!(10, reader.points_read());
+    /// ```
+    pub fn skip_points(&mut self, n: u64) -> Result<()> {
+        let position = self.points_read().saturating_add(n);
+        self.point_reader.seek(position)
+    }
+
+    /// Only decodes the selected fields of the points read from now on, e.g. to scan coordinates
+    /// quickly.
+    ///
+    /// Records are still read whole, so the reader stays aligned. See `decode_point_fields` for
+    /// what's left undecoded. Points read this way don't match the header's point format if gps
+    /// time or color is left out, so they can't be written back as is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Read, Reader};
+    /// use las::reader::FieldMask;
+    /// let mut reader = Reader::from_path("tests/data/autzen.las").unwrap().with_fields(FieldMask::coordinates());
+    /// let point = reader.read().unwrap().unwrap();
+    /// assert_eq!(None, point.gps_time);
+    /// ```
+    pub fn with_fields(mut self, fields: FieldMask) -> Reader {
+        self.point_reader.set_fields(fields);
+        self
+    }
+
+    /// Reads the next point record without decoding it.
+    ///
+    /// The bytes are the record as laid out by the header's point format, including any extra
+    /// bytes, and can be passed to `Writer::write_raw` or decoded later with
+    /// `reader::decode_point`. Returns `None` when there are no more points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Read, Reader};
+    /// let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    /// let len = reader.header().point_format().len() as usize;
+    /// assert_eq!(len, reader.next_raw().unwrap().unwrap().len());
+    /// ```
+    pub fn next_raw(&mut self) -> Result<Option<&[u8]>> {
+        match self.point_reader.read_next_raw() {
+            Some(Ok(bytes)) => Ok(Some(bytes)),
+            Some(Err(err)) => Err(err),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the byte offset of each point record in the source, indexed by point number.
+    ///
+    /// Records have a fixed length, so point `k` is at the offset to the point data plus `k`
+    /// record lengths. Use the offsets with `read_point_by_offset`. Returns `Error::Compressed`
+    /// for compressed points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Read, Reader};
+    /// let reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    /// let index = reader.build_offset_index().unwrap();
+    /// assert_eq!(106, index.len());
+    /// ```
+    pub fn build_offset_index(&self) -> Result<Vec<u64>> {
+        let start = self
+            .point_reader
+            .offset_to_point_data()
+            .ok_or(Error::Compressed)?;
+        let len = u64::from(self.header().point_format().len());
+        Ok((0..self.header().number_of_points())
+            .map(|k| start + k * len)
+            .collect())
+    }
+
+    /// Reads the point whose record starts at this byte offset, e.g. one from
+    /// `build_offset_index`.
+    ///
+    /// The reader is left at the next point, as if it had seeked to this one and read it.
+    /// Returns an error if the offset isn't the start of a record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Read, Reader};
+    /// let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    /// let index = reader.build_offset_index().unwrap();
+    /// let point = reader.read_point_by_offset(index[42]).unwrap();
+    /// reader.seek(42).unwrap();
+    /// assert_eq!(point, reader.read().unwrap().unwrap());
+    /// ```
+    pub fn read_point_by_offset(&mut self, offset: u64) -> Result<Point> {
+        let start = self
+            .point_reader
+            .offset_to_point_data()
+            .ok_or(Error::Compressed)?;
+        let len = u64::from(self.header().point_format().len());
+        let k = offset.checked_sub(start).map(|n| (n / len, n % len));
+        match k {
+            Some((k, 0)) if k < self.header().number_of_points() => {
+                self.point_reader.seek(k)?;
+                self.point_reader
+                    .read_next()
+                    .unwrap_or_else(|| Err(Error::PointOffset(offset).into()))
+            }
+            _ => Err(Error::PointOffset(offset).into()),
+        }
+    }
+
+    /// Returns the number of points that have been read, i.e. the index of the next point.
+    ///
+    /// Seeking moves this position as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Read, Reader};
+    /// let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    /// assert_eq!(0, reader.points_read());
+    /// reader.read().unwrap().unwrap();
+    /// assert_eq!(1, reader.points_read());
+    /// ```
+    pub fn points_read(&self) -> u64 {
+        self.point_reader.points_read()
+    }
+
+    /// Returns the number of points that have not yet been read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Read, Reader};
+    /// let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    /// let number_of_points = reader.header().number_of_points();
+    /// reader.read().unwrap().unwrap();
+    /// assert_eq!(number_of_points - 1, reader.points_remaining());
+    /// ```
+    pub fn points_remaining(&self) -> u64 {
+        self.header()
+            .number_of_points()
+            .saturating_sub(self.points_read())
+    }
+
+    /// Returns an empty vector with room for the remaining points.
+    ///
+    /// The capacity is capped, so a corrupt point count can't cause a huge allocation.
+    fn point_buffer(&self) -> Vec<Point> {
+        Vec::with_capacity(self.points_remaining().min(MAX_PREALLOCATED_POINTS) as usize)
+    }
+
+    /// Returns this reader's regular variable length records.
+    ///
+    /// Vlr payloads are read when the reader is created, before any points, so they can be used
+    /// without reading points. Use `Header::from_path` to read only the metadata of a file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Reader;
+    /// let reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    /// assert_eq!(4, reader.vlrs().len());
+    /// ```
+    pub fn vlrs(&self) -> &[Vlr] {
+        self.header().vlrs()
+    }
 }
 
 impl Read for Reader {
@@ -322,6 +1097,26 @@ impl Reader {
             .map_err(::Error::from)
             .and_then(|file| Reader::new(BufReader::new(file)))
     }
+
+    /// Creates a new reader from a path, buffering reads with a buffer of `buffer_size` bytes.
+    ///
+    /// `from_path` uses the standard library's default buffer size, which can be small for large
+    /// files.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use las::Reader;
+    /// let reader = Reader::from_path_with_buffer_size("tests/data/autzen.las", 1 << 20).unwrap();
+    /// ```
+    pub fn from_path_with_buffer_size<P: AsRef<Path>>(
+        path: P,
+        buffer_size: usize,
+    ) -> Result<Reader> {
+        File::open(path)
+            .map_err(::Error::from)
+            .and_then(|file| Reader::new(BufReader::with_capacity(buffer_size, file)))
+    }
 }
 
 #[cfg(test)]
@@ -330,6 +1125,383 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn not_a_las_file() {
+        use std::io::Cursor;
+
+        let text = b"x,y,z\n1.0,2.0,3.0\n".to_vec();
+        match Reader::new(Cursor::new(text)) {
+            Err(::Error::Header(::header::Error::FileSignature(signature))) => {
+                assert_eq!(*b"x,y,", signature)
+            }
+            other => panic!("expected a file signature error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn vlr_declared_too_long() {
+        use byteorder::{LittleEndian, WriteBytesExt};
+        use std::io::Cursor;
+
+        let mut builder = Builder::default();
+        builder.vlrs.push(Vlr {
+            user_id: "test".to_string(),
+            data: vec![42; 10],
+            ..Default::default()
+        });
+        let mut writer =
+            Writer::new(Cursor::new(Vec::new()), builder.into_header().unwrap()).unwrap();
+        writer
+            .write(Point {
+                x: 1.,
+                ..Default::default()
+            })
+            .unwrap();
+        let mut cursor = writer.into_inner().unwrap();
+        cursor.set_position(227 + 20);
+        cursor.write_u16::<LittleEndian>(100).unwrap();
+        cursor.set_position(0);
+        let mut reader = Reader::new(cursor).unwrap();
+        assert_eq!(1, reader.vlrs().len());
+        assert_eq!(vec![42; 10], reader.vlrs()[0].data);
+        assert_eq!(1., reader.read().unwrap().unwrap().x);
+    }
+
+    #[test]
+    fn read_decimated() {
+        let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+        let all = reader.points().collect::<Result<Vec<_>>>().unwrap();
+        reader.seek(0).unwrap();
+        let decimated = reader.read_decimated(2).unwrap();
+        assert_eq!(all.len() / 2, decimated.len());
+        let even = all.into_iter().step_by(2).collect::<Vec<_>>();
+        assert_eq!(even, decimated);
+    }
+
+    #[test]
+    fn read_decimated_huge_step() {
+        let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+        let first = reader.read().unwrap().unwrap();
+        reader.seek(0).unwrap();
+        assert_eq!(vec![first], reader.read_decimated(u64::MAX).unwrap());
+    }
+
+    #[test]
+    fn skip_points() {
+        let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+        let all = reader.points().collect::<Result<Vec<_>>>().unwrap();
+        reader.seek(0).unwrap();
+        reader.skip_points(50).unwrap();
+        assert_eq!(all[50], reader.read().unwrap().unwrap());
+        reader.skip_points(2).unwrap();
+        assert_eq!(53, reader.points_read());
+        assert_eq!(all[53], reader.read().unwrap().unwrap());
+    }
+
+    #[test]
+    fn slack_bytes_without_schema() {
+        use byteorder::{LittleEndian, WriteBytesExt};
+        use point::Format;
+        use std::io::{Cursor, Write as IoWrite};
+
+        let mut raw_header = raw::Header {
+            point_data_record_format: 1,
+            point_data_record_length: 32,
+            number_of_point_records: 2,
+            x_scale_factor: 0.01,
+            y_scale_factor: 0.01,
+            z_scale_factor: 0.01,
+            ..Default::default()
+        };
+        raw_header.number_of_points_by_return[0] = 2;
+        let mut cursor = Cursor::new(Vec::new());
+        raw_header.write_to(&mut cursor).unwrap();
+        for i in 0..2 {
+            cursor.write_i32::<LittleEndian>(100 * i).unwrap();
+            cursor.write_all(&[0; 16]).unwrap();
+            cursor.write_f64::<LittleEndian>(f64::from(i)).unwrap();
+            cursor.write_all(&[0xAB, 0xCD, 0xEF, i as u8]).unwrap();
+        }
+        cursor.set_position(0);
+        let mut reader = Reader::new(cursor).unwrap();
+        let format = reader.header().point_format();
+        assert_eq!(4, format.extra_bytes);
+        assert_eq!(Format::new(1).unwrap().len() + 4, format.len());
+        let points = reader.points().collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(2, points.len());
+        assert_eq!(1., points[1].x);
+        assert_eq!(Some(1.), points[1].gps_time);
+        assert_eq!(vec![0xAB, 0xCD, 0xEF, 1], points[1].extra_bytes);
+    }
+
+    #[test]
+    fn from_header_and_points() {
+        use std::io::Cursor;
+
+        let bytes = std::fs::read("tests/data/autzen.las").unwrap();
+        let mut reader = Reader::new(Cursor::new(bytes.clone())).unwrap();
+        let expected = reader.points().collect::<Result<Vec<_>>>().unwrap();
+
+        let header = Header::from_bytes(&bytes).unwrap();
+        let offset = header.to_bytes().unwrap().len();
+        let mut reader =
+            Reader::from_header_and_points(header, Cursor::new(bytes[offset..].to_vec())).unwrap();
+        let points = reader.points().collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(expected, points);
+    }
+
+    #[test]
+    fn validate_all() {
+        use point::Format;
+        use std::io::Cursor;
+
+        let mut builder = Builder::from((1, 4));
+        builder.point_format = Format::new(6).unwrap();
+        let mut writer =
+            Writer::new(Cursor::new(Vec::new()), builder.into_header().unwrap()).unwrap();
+        let good = Point {
+            return_number: 1,
+            number_of_returns: 1,
+            gps_time: Some(0.),
+            ..Default::default()
+        };
+        for i in 0..10 {
+            let mut point = good.clone();
+            if i == 3 {
+                point.return_number = 2;
+            }
+            if i == 7 {
+                point.classification = Classification::Reserved(40);
+            }
+            writer.write(point).unwrap();
+        }
+        let mut reader = Reader::new(writer.into_inner().unwrap()).unwrap();
+
+        let report = reader.validate_all().unwrap();
+        assert_eq!(10, report.points_checked);
+        assert_eq!(1, report.returns.count);
+        assert_eq!(vec![3], report.returns.examples);
+        assert_eq!(1, report.classification.count);
+        assert_eq!(vec![7], report.classification.examples);
+        assert_eq!(0, report.coordinates.count);
+        assert!(!report.is_valid());
+    }
+
+    #[test]
+    fn with_fields_coordinates() {
+        use point::Format;
+        use std::io::Cursor;
+
+        let mut builder = Builder::default();
+        builder.point_format = Format::new(1).unwrap();
+        let mut writer =
+            Writer::new(Cursor::new(Vec::new()), builder.into_header().unwrap()).unwrap();
+        let points = (0..3)
+            .map(|i| Point {
+                x: f64::from(i),
+                y: 2. * f64::from(i),
+                z: -1.5,
+                intensity: 42,
+                gps_time: Some(f64::from(i)),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        for point in &points {
+            writer.write(point.clone()).unwrap();
+        }
+        let cursor = writer.into_inner().unwrap();
+        let mut reader = Reader::new(cursor)
+            .unwrap()
+            .with_fields(FieldMask::coordinates());
+        let read = reader.points().collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(points.len(), read.len());
+        for (point, read) in points.iter().zip(&read) {
+            assert_eq!((point.x, point.y, point.z), (read.x, read.y, read.z));
+            assert_eq!(0, read.intensity);
+            assert_eq!(None, read.gps_time);
+        }
+    }
+
+    #[test]
+    fn decode_point_fields_offsets() {
+        use point::{Classification, Format};
+        use Color;
+
+        for n in &[3, 5, 7, 8] {
+            let mut builder = Builder::from((1, 4));
+            builder.point_format = Format::new(*n).unwrap();
+            let header = builder.into_header().unwrap();
+            let mut point = Point {
+                x: 1.,
+                y: 2.,
+                z: 3.,
+                intensity: 4,
+                return_number: 1,
+                number_of_returns: 2,
+                classification: Classification::Ground,
+                gps_time: Some(5.),
+                color: Some(Color::new(6, 7, 8)),
+                ..Default::default()
+            };
+            if header.point_format().has_nir {
+                point.nir = Some(9);
+            }
+            if header.point_format().has_waveform {
+                point.waveform = Some(Default::default());
+            }
+            let mut bytes = Vec::new();
+            ::writer::encode_point(&point, &header, &mut bytes).unwrap();
+            let fields = FieldMask {
+                intensity: true,
+                classification: true,
+                gps_time: true,
+                color: true,
+            };
+            let mut all_but_one = fields;
+            all_but_one.color = false;
+            let decoded = decode_point_fields(&bytes, &header, all_but_one).unwrap();
+            assert_eq!(point.intensity, decoded.intensity);
+            assert_eq!(point.return_number, decoded.return_number);
+            assert_eq!(point.classification, decoded.classification);
+            assert_eq!(point.gps_time, decoded.gps_time);
+            assert_eq!(None, decoded.color);
+            let mut only_color = FieldMask::coordinates();
+            only_color.color = true;
+            let decoded = decode_point_fields(&bytes, &header, only_color).unwrap();
+            assert_eq!(point.color, decoded.color);
+        }
+    }
+
+    #[test]
+    fn build_offset_index() {
+        let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+        let index = reader.build_offset_index().unwrap();
+        let raw_header = reader.header().clone().into_raw().unwrap();
+        let start = u64::from(raw_header.offset_to_point_data);
+        let len = u64::from(raw_header.point_data_record_length);
+        for (k, &offset) in index.iter().enumerate() {
+            assert_eq!(start + k as u64 * len, offset);
+        }
+
+        let points = reader.points().collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(points[7], reader.read_point_by_offset(index[7]).unwrap());
+        assert_eq!(8, reader.points_read());
+        assert!(reader.read_point_by_offset(index[7] + 1).is_err());
+        assert!(reader.read_point_by_offset(start - 1).is_err());
+        assert!(reader.read_point_by_offset(start + 106 * len).is_err());
+    }
+
+    #[test]
+    fn vlr_declared_too_long_before_other_vlrs() {
+        use byteorder::{LittleEndian, WriteBytesExt};
+        use std::io::Cursor;
+
+        let mut builder = Builder::default();
+        for (i, user_id) in ["first", "second", "third"].iter().enumerate() {
+            builder.vlrs.push(Vlr {
+                user_id: user_id.to_string(),
+                data: vec![i as u8; 10],
+                ..Default::default()
+            });
+        }
+        let mut writer =
+            Writer::new(Cursor::new(Vec::new()), builder.into_header().unwrap()).unwrap();
+        writer
+            .write(Point {
+                x: 1.,
+                ..Default::default()
+            })
+            .unwrap();
+        let mut cursor = writer.into_inner().unwrap();
+        cursor.set_position(227 + 20);
+        cursor.write_u16::<LittleEndian>(80).unwrap();
+        cursor.set_position(0);
+        let mut reader = Reader::new(cursor).unwrap();
+        let vlrs = reader.vlrs().clone();
+        assert_eq!(3, vlrs.len());
+        assert_eq!(vec![0; 10], vlrs[0].data);
+        assert_eq!("second", vlrs[1].user_id);
+        assert_eq!(vec![1; 10], vlrs[1].data);
+        assert_eq!("third", vlrs[2].user_id);
+        assert_eq!(vec![2; 10], vlrs[2].data);
+        assert!(reader.header().vlr_padding().is_empty());
+        assert_eq!(1., reader.read().unwrap().unwrap().x);
+    }
+
+    #[test]
+    fn vlr_with_header_like_data_before_vlr_without_user_id() {
+        use std::io::Cursor;
+
+        let mut data = vec![0; 60];
+        data[2] = b'x';
+        let mut builder = Builder::default();
+        builder.vlrs.push(Vlr {
+            user_id: "mine".to_string(),
+            data: data.clone(),
+            ..Default::default()
+        });
+        builder.vlrs.push(Vlr {
+            data: vec![9; 4],
+            ..Default::default()
+        });
+        let writer = Writer::new(Cursor::new(Vec::new()), builder.into_header().unwrap()).unwrap();
+        let reader = Reader::new(writer.into_inner().unwrap()).unwrap();
+        let vlrs = reader.header().vlrs();
+        assert_eq!(2, vlrs.len());
+        assert_eq!("mine", vlrs[0].user_id);
+        assert_eq!(data, vlrs[0].data);
+        assert_eq!("", vlrs[1].user_id);
+        assert_eq!(vec![9; 4], vlrs[1].data);
+        assert!(reader.header().vlr_padding().is_empty());
+    }
+
+    #[test]
+    fn decode_point_format_3() {
+        use byteorder::{LittleEndian, WriteBytesExt};
+        use point::{Classification, Format, ScanDirection};
+        use Color;
+
+        let mut builder = Builder::default();
+        builder.point_format = Format::new(3).unwrap();
+        let header = builder.into_header().unwrap();
+
+        let mut bytes = Vec::new();
+        bytes.write_i32::<LittleEndian>(1000).unwrap();
+        bytes.write_i32::<LittleEndian>(-2000).unwrap();
+        bytes.write_i32::<LittleEndian>(3000).unwrap();
+        bytes.write_u16::<LittleEndian>(42).unwrap();
+        bytes.write_u8(0b1101_0010).unwrap();
+        bytes.write_u8(0b1010_0010).unwrap();
+        bytes.write_i8(-12).unwrap();
+        bytes.write_u8(7).unwrap();
+        bytes.write_u16::<LittleEndian>(1234).unwrap();
+        bytes.write_f64::<LittleEndian>(5.5).unwrap();
+        bytes.write_u16::<LittleEndian>(1).unwrap();
+        bytes.write_u16::<LittleEndian>(2).unwrap();
+        bytes.write_u16::<LittleEndian>(3).unwrap();
+        assert_eq!(usize::from(header.point_format().len()), bytes.len());
+
+        let point = decode_point(&bytes, &header).unwrap();
+        assert_eq!(1., point.x);
+        assert_eq!(-2., point.y);
+        assert_eq!(3., point.z);
+        assert_eq!(42, point.intensity);
+        assert_eq!(2, point.return_number);
+        assert_eq!(2, point.number_of_returns);
+        assert_eq!(ScanDirection::LeftToRight, point.scan_direction);
+        assert!(point.is_edge_of_flight_line);
+        assert_eq!(Classification::Ground, point.classification);
+        assert!(point.is_synthetic);
+        assert!(!point.is_key_point);
+        assert!(point.is_withheld);
+        assert!(!point.is_overlap);
+        assert_eq!(-12., point.scan_angle);
+        assert_eq!(7, point.user_data);
+        assert_eq!(1234, point.point_source_id);
+        assert_eq!(Some(5.5), point.gps_time);
+        assert_eq!(Some(Color::new(1, 2, 3)), point.color);
+    }
+
     #[test]
     fn seek() {
         let mut writer = Writer::default();
@@ -346,4 +1518,239 @@ mod tests {
         assert_eq!(point, reader.read().unwrap().unwrap());
         assert_eq!(reader.read().is_none(), true);
     }
+
+    #[test]
+    fn points_read_and_remaining() {
+        let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+        let number_of_points = reader.header().number_of_points();
+        for _ in 0..number_of_points / 2 {
+            reader.read().unwrap().unwrap();
+        }
+        assert_eq!(number_of_points / 2, reader.points_read());
+        assert_eq!(
+            number_of_points,
+            reader.points_read() + reader.points_remaining()
+        );
+        reader.seek(number_of_points).unwrap();
+        assert_eq!(0, reader.points_remaining());
+    }
+
+    #[test]
+    fn bulk_reads_preallocate() {
+        let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+        let number_of_points = reader.header().number_of_points() as usize;
+        let points = reader
+            .read_all_cancellable(&AtomicBool::new(false))
+            .unwrap();
+        assert_eq!(number_of_points, points.len());
+        assert!(points.capacity() >= number_of_points);
+    }
+
+    #[test]
+    fn read_all_cancellable() {
+        use std::io::Cursor;
+        use std::sync::Arc;
+
+        #[derive(Debug)]
+        struct CancelAfter {
+            inner: Cursor<Vec<u8>>,
+            position: u64,
+            cancel: Arc<AtomicBool>,
+        }
+
+        impl std::io::Read for CancelAfter {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                if self.inner.position() >= self.position {
+                    self.cancel.store(true, Ordering::Relaxed);
+                }
+                self.inner.read(buf)
+            }
+        }
+
+        impl Seek for CancelAfter {
+            fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+                self.inner.seek(pos)
+            }
+        }
+
+        let mut writer = Writer::default();
+        for _ in 0..100 {
+            writer.write(Point::default()).unwrap();
+        }
+        let bytes = writer.into_inner().unwrap().into_inner();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let position = bytes.len() as u64 - 50 * 20;
+        let mut reader = Reader::new(CancelAfter {
+            inner: Cursor::new(bytes),
+            position,
+            cancel: cancel.clone(),
+        })
+        .unwrap();
+        match reader.read_all_cancellable(&cancel) {
+            Err(::Error::Reader(Error::Cancelled)) => {}
+            other => panic!("expected a cancelled read, got {:?}", other),
+        }
+        assert!(reader.points_read() > 0);
+        assert!(reader.points_read() < 100);
+    }
+
+    #[test]
+    fn trailing_bytes_roundtrip() {
+        use std::io::Cursor;
+
+        let mut builder = Builder::from((1, 2));
+        builder.point_padding = b"user data".to_vec();
+        let mut writer =
+            Writer::new(Cursor::new(Vec::new()), builder.into_header().unwrap()).unwrap();
+        for _ in 0..3 {
+            writer.write(Point::default()).unwrap();
+        }
+        let bytes = writer.into_inner().unwrap().into_inner();
+        assert!(bytes.ends_with(b"user data"));
+
+        let mut reader = Reader::new(Cursor::new(bytes.clone())).unwrap();
+        assert_eq!(b"user data", reader.header().point_padding().as_slice());
+        let points = reader.points().collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(3, points.len());
+        let mut writer = Writer::new(Cursor::new(Vec::new()), reader.header().clone()).unwrap();
+        for point in points {
+            writer.write(point).unwrap();
+        }
+        assert_eq!(bytes, writer.into_inner().unwrap().into_inner());
+    }
+
+    #[test]
+    fn trailing_bytes_not_read_with_header_only() {
+        let mut writer = Writer::default();
+        writer.write(Point::default()).unwrap();
+        let mut bytes = writer.into_inner().unwrap().into_inner();
+        bytes.extend_from_slice(b"user data");
+        let header = Header::from_bytes(&bytes).unwrap();
+        assert!(header.point_padding().is_empty());
+    }
+
+    #[test]
+    fn trailing_bytes_over_limit() {
+        use std::io::Cursor;
+
+        let mut writer = Writer::default();
+        writer.write(Point::default()).unwrap();
+        let mut bytes = writer.into_inner().unwrap().into_inner();
+        let len = bytes.len();
+        bytes.resize(len + MAX_TRAILING_POINT_PADDING as usize + 1, 42);
+        let mut reader = Reader::new(Cursor::new(bytes)).unwrap();
+        assert!(reader.header().point_padding().is_empty());
+        assert_eq!(1, reader.points().count());
+    }
+
+    #[test]
+    fn padding_roundtrip_is_byte_for_byte() {
+        let mut builder = Builder::from((1, 4));
+        builder.padding = b"header padding".to_vec();
+        builder.vlrs.push(Vlr::default());
+        builder.vlr_padding = b"vendor padding".to_vec();
+        builder.point_padding = b"user bytes".to_vec();
+        builder.evlrs.push(Vlr::default());
+        let mut writer = Writer::new(
+            std::io::Cursor::new(Vec::new()),
+            builder.into_header().unwrap(),
+        )
+        .unwrap();
+        writer.write(Default::default()).unwrap();
+        let bytes = writer.into_inner().unwrap().into_inner();
+
+        let mut reader = Reader::new(std::io::Cursor::new(bytes.clone())).unwrap();
+        assert_eq!(b"vendor padding", reader.header().vlr_padding().as_slice());
+        assert_eq!(b"user bytes", reader.header().point_padding().as_slice());
+        let mut writer =
+            Writer::new(std::io::Cursor::new(Vec::new()), reader.header().clone()).unwrap();
+        for point in reader.points() {
+            writer.write(point.unwrap()).unwrap();
+        }
+        assert_eq!(bytes, writer.into_inner().unwrap().into_inner());
+    }
+
+    #[test]
+    fn read_all_lenient_truncated() {
+        let mut writer = Writer::default();
+        for i in 0..3 {
+            writer
+                .write(Point {
+                    x: f64::from(i),
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+        let mut bytes = writer.into_inner().unwrap().into_inner();
+        let len = bytes.len() - 30;
+        bytes.truncate(len);
+        let mut reader = Reader::new_lenient(std::io::Cursor::new(bytes)).unwrap();
+        let (points, warnings) = reader.read_all_lenient();
+        assert_eq!(1, points.len());
+        assert_eq!(0., points[0].x);
+        assert_eq!(1, warnings.len());
+        assert_eq!(1, warnings[0].index);
+    }
+
+    #[test]
+    fn point_count_mismatch() {
+        use byteorder::{LittleEndian, WriteBytesExt};
+
+        let mut writer = Writer::default();
+        writer.write(Default::default()).unwrap();
+        let mut cursor = writer.into_inner().unwrap();
+        cursor.set_position(107);
+        cursor.write_u32::<LittleEndian>(2).unwrap();
+        cursor.set_position(0);
+        let mut reader = Reader::new_lenient(cursor).unwrap();
+        assert!(reader.read().unwrap().is_ok());
+        match reader.read().unwrap().unwrap_err() {
+            ::Error::Reader(Error::PointCountMismatch { declared, actual }) => {
+                assert_eq!(2, declared);
+                assert_eq!(1, actual);
+            }
+            err => panic!("unexpected error: {}", err),
+        }
+    }
+
+    #[test]
+    fn truncated_file() {
+        let mut writer = Writer::default();
+        for i in 0..4 {
+            writer
+                .write(Point {
+                    x: f64::from(i),
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+        let mut bytes = writer.into_inner().unwrap().into_inner();
+        let len = bytes.len() - 2 * 20;
+        bytes.truncate(len);
+        match Reader::new(std::io::Cursor::new(bytes)).unwrap_err() {
+            ::Error::Reader(Error::TruncatedFile { expected, actual }) => {
+                assert_eq!(len as u64 + 40, expected);
+                assert_eq!(len as u64, actual);
+            }
+            err => panic!("unexpected error: {}", err),
+        }
+    }
+
+    #[test]
+    fn point_count_larger_than_file() {
+        use byteorder::{LittleEndian, WriteBytesExt};
+
+        let mut writer = Writer::default();
+        writer.write(Default::default()).unwrap();
+        let mut cursor = writer.into_inner().unwrap();
+        cursor.set_position(107);
+        cursor.write_u32::<LittleEndian>(2).unwrap();
+        cursor.set_position(0);
+        match Reader::new(cursor).unwrap_err() {
+            ::Error::Reader(Error::TruncatedFile { expected, actual }) => {
+                assert_eq!(expected, actual + 20);
+            }
+            err => panic!("unexpected error: {}", err),
+        }
+    }
 }
@@ -71,6 +71,18 @@ mod tests {
     use super::*;
     use std::i32;
 
+    #[test]
+    fn roundtrip() {
+        let transform = Transform {
+            scale: 0.01,
+            offset: 1000.,
+        };
+        for &n in &[1000., 1234.567, -42.1234, 0.005] {
+            let m = transform.direct(transform.inverse(n).unwrap());
+            assert!((n - m).abs() <= transform.scale / 2. + 1e-9);
+        }
+    }
+
     #[test]
     fn too_large() {
         let transform = Transform::default();
@@ -11,6 +11,16 @@ pub trait FromLasStr {
     fn from_las_str(&mut self, s: &str) -> Result<()>;
 }
 
+/// Hashes bytes with the 64-bit FNV-1a algorithm, which is stable across platforms and runs.
+pub fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+pub const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
 pub fn some_or_none_if_zero<T: Zero>(n: T) -> Option<T> {
     if n.is_zero() {
         None
@@ -95,6 +105,13 @@ mod tests {
         assert_eq!(b"Beer!", &bytes);
     }
 
+    #[test]
+    fn fnv1a_known_values() {
+        assert_eq!(FNV_OFFSET_BASIS, fnv1a(FNV_OFFSET_BASIS, b""));
+        assert_eq!(0xaf63_dc4c_8601_ec8c, fnv1a(FNV_OFFSET_BASIS, b"a"));
+        assert_eq!(0x8594_4171_f739_67e8, fnv1a(FNV_OFFSET_BASIS, b"foobar"));
+    }
+
     #[test]
     fn from_too_long() {
         let mut bytes = [0; 5];
@@ -131,6 +131,23 @@ impl Version {
             _ => false,
         }
     }
+
+    /// Returns the point formats that this version supports, in increasing order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Version;
+    /// let formats = Version::new(1, 3).supported_point_formats();
+    /// assert_eq!(6, formats.len());
+    /// assert_eq!(5, formats[5].to_u8().unwrap());
+    /// ```
+    pub fn supported_point_formats(&self) -> Vec<Format> {
+        (0..=10)
+            .map(|n| Format::new(n).expect("formats 0 through 10 should be valid"))
+            .filter(|&format| self.supports_point_format(format))
+            .collect()
+    }
 }
 
 impl Default for Version {
@@ -192,6 +209,15 @@ mod tests {
                         }
                     }
                 }
+
+                #[test]
+                fn supported_point_formats() {
+                    let formats = Version::new($major, $minor).supported_point_formats();
+                    assert_eq!(($max_point_format + 1) as usize, formats.len());
+                    for (n, format) in formats.iter().enumerate() {
+                        assert_eq!(n as u8, format.to_u8().unwrap());
+                    }
+                }
             }
         };
     }
@@ -203,4 +229,14 @@ mod tests {
     version!(las_1_4, 1, 4, [true; 5], 10);
     version!(las_1_5, 1, 5, [false; 5], -1);
     version!(las_2_0, 2, 0, [false; 5], -1);
+
+    #[test]
+    fn las_1_2_supports_formats_0_through_3() {
+        let formats: Vec<u8> = Version::new(1, 2)
+            .supported_point_formats()
+            .iter()
+            .map(|format| format.to_u8().unwrap())
+            .collect();
+        assert_eq!(vec![0, 1, 2, 3], formats);
+    }
 }
@@ -32,16 +32,17 @@
 //! ```
 
 use std::fmt::Debug;
-use std::fs::File;
+use std::fs::{self, File, OpenOptions};
 use std::io::{BufWriter, Cursor, Seek, SeekFrom};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[cfg(feature = "laz")]
 use compression::CompressedPointWriter;
 
 use point::Format;
+use reader::decode_point;
 use thiserror::Error;
-use {Header, Point, Result};
+use {Bounds, Builder, Header, Point, Read, Reader, Result, Transform, Version};
 
 /// Writer errors.
 #[derive(Error, Debug)]
@@ -55,25 +56,79 @@ pub enum Error {
     #[allow(missing_docs)]
     PointAttributes { format: Format, point: Point },
 
+    /// A point written to a writer doesn't match the writer's point format.
+    #[error("the `{field}` of point {index} does not match the point format ({format})")]
+    #[allow(missing_docs)]
+    PointFormat {
+        index: u64,
+        field: &'static str,
+        format: Format,
+    },
+
+    /// A raw point record doesn't have the length of the point format.
+    #[error("the raw point record is {actual} bytes, but the point format's records are {expected} bytes")]
+    #[allow(missing_docs)]
+    RecordLength { expected: u16, actual: usize },
+
     /// Wrapper around `std::io::Error`.
     #[error(transparent)]
     Io(#[from] std::io::Error),
 }
 
+/// Encodes a point as a record for a header's point format and transforms, appending it to `buf`.
+///
+/// Returns an error if the point doesn't match the point format or can't be represented with
+/// the header's transforms, in which case `buf` is left unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use las::{Header, Point};
+/// use las::reader::decode_point;
+/// use las::writer::encode_point;
+/// let header = Header::default();
+/// let point = Point { x: 1., intensity: 42, ..Default::default() };
+/// let mut buf = Vec::new();
+/// encode_point(&point, &header, &mut buf).unwrap();
+/// assert_eq!(header.point_format().len() as usize, buf.len());
+/// assert_eq!(point, decode_point(&buf, &header).unwrap());
+/// ```
+pub fn encode_point(point: &Point, header: &Header, buf: &mut Vec<u8>) -> Result<()> {
+    let format = header.point_format();
+    if !point.matches(format) {
+        return Err(Error::PointAttributes {
+            format: *format,
+            point: point.clone(),
+        }
+        .into());
+    }
+    let len = buf.len();
+    let result = point
+        .clone()
+        .into_raw(header.transforms())
+        .and_then(|raw_point| raw_point.write_to(&mut *buf, format));
+    if result.is_err() {
+        buf.truncate(len);
+    }
+    result
+}
+
+#[cfg(feature = "laz")]
 pub(crate) fn write_point_to<W: std::io::Write>(
-    mut dst: &mut W,
-    point: Point,
+    dst: &mut W,
+    point: &Point,
     header: &Header,
 ) -> Result<()> {
-    point
-        .into_raw(header.transforms())
-        .and_then(|raw_point| raw_point.write_to(&mut dst, header.point_format()))?;
+    let mut buf = Vec::with_capacity(usize::from(header.point_format().len()));
+    encode_point(point, header, &mut buf)?;
+    dst.write_all(&buf)?;
     Ok(())
 }
 
 /// Trait that defines a PointWriter, s
 pub(crate) trait PointWriter<W: std::io::Write>: Debug {
     fn write_next(&mut self, point: Point) -> Result<()>;
+    fn write_next_raw(&mut self, bytes: &[u8]) -> Result<()>;
     //https://users.rust-lang.org/t/is-there-a-way-to-move-a-trait-object/707
     fn into_inner(self: Box<Self>) -> W;
     fn get_mut(&mut self) -> &mut W;
@@ -92,6 +147,10 @@ impl<W: std::io::Write> PointWriter<W> for UnreachablePointWriter {
         unreachable!()
     }
 
+    fn write_next_raw(&mut self, _bytes: &[u8]) -> Result<()> {
+        unreachable!()
+    }
+
     fn into_inner(self: Box<Self>) -> W {
         unreachable!()
     }
@@ -113,12 +172,23 @@ impl<W: std::io::Write> PointWriter<W> for UnreachablePointWriter {
 struct UncompressedPointWriter<W: std::io::Write + Debug> {
     dest: W,
     header: Header,
+    buffer: Vec<u8>,
 }
 
 impl<W: std::io::Write + Debug> PointWriter<W> for UncompressedPointWriter<W> {
     fn write_next(&mut self, point: Point) -> Result<()> {
+        self.buffer.clear();
+        encode_point(&point, &self.header, &mut self.buffer)?;
+        self.dest.write_all(&self.buffer)?;
+        self.header.add_point(&point);
+        Ok(())
+    }
+
+    fn write_next_raw(&mut self, bytes: &[u8]) -> Result<()> {
+        // The point is only decoded to keep the header's bounds and counts up to date.
+        let point = decode_point(bytes, &self.header)?;
+        self.dest.write_all(bytes)?;
         self.header.add_point(&point);
-        write_point_to(&mut self.dest, point, &self.header)?;
         Ok(())
     }
 
@@ -188,6 +258,141 @@ pub trait Write {
     fn write(&mut self, point: Point) -> Result<()>;
 }
 
+/// Options for adapting a header to the points before they are written.
+///
+/// Used by `Writer::write_with_options`. Point counts are always recomputed from the written
+/// points. By default the bounds are too, see `recompute_bounds`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WriteOptions {
+    /// Writes the bounds of the written points rather than the header's bounds.
+    ///
+    /// Defaults to true. Turn it off to keep bounds that were set on purpose, e.g. a tile's
+    /// extent.
+    pub recompute_bounds: bool,
+
+    /// Sets each axis offset to the floor of the points' minimum value.
+    pub auto_offsets: bool,
+
+    /// Sets each axis scale to the finest power of ten that still fits the points into `i32`.
+    pub optimize_scale: bool,
+
+    /// Replaces the header's point format with one inferred from the points via `Format::infer`.
+    pub infer_format: bool,
+
+    /// Converts the header to this las version, see `Header::set_version`.
+    pub version: Option<Version>,
+
+    /// Pads the vlr padding with zeros so the offset to the point data is a multiple of this many
+    /// bytes.
+    ///
+    /// The padding is read back as the header's vlr padding, so it roundtrips. Compressed output
+    /// isn't aligned, since the laszip vlr is added while writing.
+    pub point_data_alignment: Option<u32>,
+
+    /// Writes zeros for the number of points by return if the header has synthetic return
+    /// numbers, since those don't describe real returns.
+    ///
+    /// The total number of points is still written. Headers without synthetic return numbers are
+    /// counted as usual.
+    pub skip_synthetic_return_counts: bool,
+}
+
+impl Default for WriteOptions {
+    fn default() -> WriteOptions {
+        WriteOptions {
+            recompute_bounds: true,
+            auto_offsets: false,
+            optimize_scale: false,
+            infer_format: false,
+            version: None,
+            point_data_alignment: None,
+            skip_synthetic_return_counts: false,
+        }
+    }
+}
+
+impl WriteOptions {
+    /// Returns a copy of this header adapted to these points according to these options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Header, Point, WriteOptions};
+    /// let points = vec![Point { x: 1000.5, gps_time: Some(1.), ..Default::default() }];
+    /// let options = WriteOptions { auto_offsets: true, infer_format: true, ..Default::default() };
+    /// let header = options.apply(&Header::default(), &points).unwrap();
+    /// assert_eq!(1000., header.transforms().x.offset);
+    /// assert!(header.point_format().has_gps_time);
+    /// ```
+    pub fn apply(&self, header: &Header, points: &[Point]) -> Result<Header> {
+        let mut builder = Builder::from(header.clone());
+        if let Some(version) = self.version {
+            builder.version = version;
+        }
+        if self.infer_format {
+            let is_compressed = builder.point_format.is_compressed;
+            builder.point_format = Format::infer(points);
+            builder.point_format.is_compressed = is_compressed;
+        }
+        if !points.is_empty() && (self.auto_offsets || self.optimize_scale) {
+            let mut bounds = Bounds::default();
+            for point in points {
+                bounds.grow(point);
+            }
+            let transforms = &mut builder.transforms;
+            for &mut (ref mut transform, min, max) in &mut [
+                (&mut transforms.x, bounds.min.x, bounds.max.x),
+                (&mut transforms.y, bounds.min.y, bounds.max.y),
+                (&mut transforms.z, bounds.min.z, bounds.max.z),
+            ] {
+                if self.auto_offsets {
+                    transform.offset = min.floor();
+                }
+                if self.optimize_scale {
+                    optimize_scale(transform, min, max);
+                }
+            }
+        }
+        let header = builder.into_header()?;
+        match self.point_data_alignment {
+            Some(alignment) if alignment > 1 => align_point_data(header, alignment),
+            _ => Ok(header),
+        }
+    }
+}
+
+fn align_point_data(header: Header, alignment: u32) -> Result<Header> {
+    let offset = header.clone().into_raw()?.offset_to_point_data;
+    let n = (alignment - offset % alignment) % alignment;
+    pad_point_data(header, n)
+}
+
+fn pad_point_data(header: Header, n: u32) -> Result<Header> {
+    if n == 0 {
+        return Ok(header);
+    }
+    let mut builder = Builder::from(header);
+    // The point data start signature must stay at the very end of the padding.
+    let at = if builder.version.requires_point_data_start_signature() {
+        builder.vlr_padding.len() - 2
+    } else {
+        builder.vlr_padding.len()
+    };
+    builder.vlr_padding.splice(at..at, vec![0; n as usize]);
+    builder.into_header()
+}
+
+fn optimize_scale(transform: &mut Transform, min: f64, max: f64) {
+    let extent = (min - transform.offset)
+        .abs()
+        .max((max - transform.offset).abs());
+    if extent == 0. || !extent.is_finite() {
+        return;
+    }
+    let exponent = (extent / f64::from(i32::MAX)).log10().ceil();
+    transform.scale = 10f64.powf(exponent);
+}
+
 /// Writes LAS data.
 ///
 /// The LAS header needs to be re-written when the writer closes. For convenience, this is done via
@@ -214,7 +419,9 @@ impl<W: 'static + std::io::Write + Seek + Debug> Writer<W> {
     /// Creates a new writer.
     ///
     /// The header that is passed in will have various fields zero'd, e.g. bounds, number of
-    /// points, etc.
+    /// points, etc., and an empty generating software or missing date filled in, see
+    /// `Header::stamp`. Returns an error if the header's transforms are invalid, see
+    /// `Header::validate`.
     ///
     /// # Examples
     ///
@@ -224,8 +431,10 @@ impl<W: 'static + std::io::Write + Seek + Debug> Writer<W> {
     /// let writer = Writer::new(Cursor::new(Vec::new()), Default::default());
     /// ```
     pub fn new(mut dest: W, mut header: Header) -> Result<Self> {
+        header.validate()?;
         let start = dest.seek(SeekFrom::Current(0))?;
         header.clear();
+        header.stamp();
 
         #[cfg(feature = "laz")]
         {
@@ -240,7 +449,11 @@ impl<W: 'static + std::io::Write + Seek + Debug> Writer<W> {
                 Ok(Self {
                     closed: false,
                     start,
-                    point_writer: Box::new(UncompressedPointWriter { dest, header }),
+                    point_writer: Box::new(UncompressedPointWriter {
+                        dest,
+                        header,
+                        buffer: Vec::new(),
+                    }),
                 })
             }
         }
@@ -250,7 +463,11 @@ impl<W: 'static + std::io::Write + Seek + Debug> Writer<W> {
             Ok(Writer {
                 closed: false,
                 start,
-                point_writer: Box::new(UncompressedPointWriter { dest, header }),
+                point_writer: Box::new(UncompressedPointWriter {
+                    dest,
+                    header,
+                    buffer: Vec::new(),
+                }),
             })
         }
     }
@@ -314,10 +531,12 @@ impl<W: 'static + std::io::Write + Seek + Debug> Write for Writer<W> {
         if self.closed {
             return Err(Error::Closed.into());
         }
-        if !point.matches(self.header().point_format()) {
-            return Err(Error::PointAttributes {
-                format: *self.header().point_format(),
-                point: point,
+        let format = *self.header().point_format();
+        if let Some(field) = point.mismatched_field(&format) {
+            return Err(Error::PointFormat {
+                index: self.header().number_of_points(),
+                field,
+                format,
             }
             .into());
         }
@@ -325,6 +544,40 @@ impl<W: 'static + std::io::Write + Seek + Debug> Write for Writer<W> {
     }
 }
 
+impl<W: 'static + std::io::Write + Seek + Debug> Writer<W> {
+    /// Writes a raw point record, e.g. one returned by `Reader::next_raw`.
+    ///
+    /// The record must be laid out for this writer's point format and transforms, since it's
+    /// written as is. Returns an error if its length doesn't match the point format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Read, Reader, Write, Writer};
+    /// let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    /// let header = reader.header().clone();
+    /// let mut writer = Writer::new(std::io::Cursor::new(Vec::new()), header).unwrap();
+    /// while let Some(bytes) = reader.next_raw().unwrap() {
+    ///     writer.write_raw(bytes).unwrap();
+    /// }
+    /// assert_eq!(106, writer.header().number_of_points());
+    /// ```
+    pub fn write_raw(&mut self, bytes: &[u8]) -> Result<()> {
+        if self.closed {
+            return Err(Error::Closed.into());
+        }
+        let expected = self.header().point_format().len();
+        if bytes.len() != usize::from(expected) {
+            return Err(Error::RecordLength {
+                expected,
+                actual: bytes.len(),
+            }
+            .into());
+        }
+        self.point_writer.write_next_raw(bytes)
+    }
+}
+
 impl<W: 'static + std::io::Write + Seek + Debug> Writer<W> {
     /// Closes this writer and returns its inner `Write`, seeked to the beginning of the las data.
     ///
@@ -353,6 +606,96 @@ impl<W: 'static + std::io::Write + Seek + Debug> Writer<W> {
         inner.seek(SeekFrom::Start(self.start))?;
         Ok(inner)
     }
+
+    /// Writes these points with a header adapted by these options, returning the inner `Write`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use las::{Header, Point, Writer, WriteOptions};
+    /// let points = vec![Point { gps_time: Some(1.), ..Default::default() }];
+    /// let options = WriteOptions { infer_format: true, ..Default::default() };
+    /// let cursor = Writer::write_with_options(Cursor::new(Vec::new()), Header::default(), &points, options).unwrap();
+    /// ```
+    pub fn write_with_options(
+        dest: W,
+        header: Header,
+        points: &[Point],
+        options: WriteOptions,
+    ) -> Result<W> {
+        let header = options.apply(&header, points)?;
+        let bounds = header.bounds();
+        let skip_return_counts =
+            options.skip_synthetic_return_counts && header.has_synthetic_return_numbers();
+        let mut writer = Writer::new(dest, header)?;
+        for point in points {
+            writer.write(point.clone())?;
+        }
+        if options.recompute_bounds && !skip_return_counts {
+            return writer.into_inner();
+        }
+        let mut raw_header = writer.header().clone().into_raw()?;
+        let mut dest = writer.into_inner()?;
+        let start = dest.stream_position()?;
+        if !options.recompute_bounds {
+            raw_header.min_x = bounds.min.x;
+            raw_header.min_y = bounds.min.y;
+            raw_header.min_z = bounds.min.z;
+            raw_header.max_x = bounds.max.x;
+            raw_header.max_y = bounds.max.y;
+            raw_header.max_z = bounds.max.z;
+        }
+        if skip_return_counts {
+            raw_header.number_of_points_by_return = Default::default();
+            if let Some(ref mut large_file) = raw_header.large_file {
+                large_file.number_of_points_by_return = Default::default();
+            }
+        }
+        raw_header.write_to(&mut dest)?;
+        dest.seek(SeekFrom::Start(start))?;
+        Ok(dest)
+    }
+
+    /// Writes these points with this header's transforms and bounds, returning the inner `Write`.
+    ///
+    /// Unlike `write_with_options`, nothing about the header is adapted to the points: the scale,
+    /// offset, and bounds are written verbatim, and only the point counts are updated. Reading a
+    /// file and writing its points back this way reproduces the file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use las::{Read, Reader, Writer};
+    /// let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    /// let header = reader.header().clone();
+    /// let points = reader.points().collect::<Result<Vec<_>, _>>().unwrap();
+    /// let cursor = Writer::write_preserving_header(Cursor::new(Vec::new()), header.clone(), &points).unwrap();
+    /// assert_eq!(header.bounds(), Reader::new(cursor).unwrap().header().bounds());
+    /// ```
+    pub fn write_preserving_header(dest: W, header: Header, points: &[Point]) -> Result<W> {
+        let original = header.clone().into_raw()?;
+        let mut writer = Writer::new(dest, header)?;
+        for point in points {
+            writer.write(point.clone())?;
+        }
+        let mut raw_header = writer.header().clone().into_raw()?;
+        let mut dest = writer.into_inner()?;
+        let start = dest.stream_position()?;
+        raw_header.min_x = original.min_x;
+        raw_header.min_y = original.min_y;
+        raw_header.min_z = original.min_z;
+        raw_header.max_x = original.max_x;
+        raw_header.max_y = original.max_y;
+        raw_header.max_z = original.max_z;
+        raw_header.generating_software = original.generating_software;
+        raw_header.file_creation_day_of_year = original.file_creation_day_of_year;
+        raw_header.file_creation_year = original.file_creation_year;
+        raw_header.write_to(&mut dest)?;
+        dest.seek(SeekFrom::Start(start))?;
+        Ok(dest)
+    }
 }
 
 impl Writer<BufWriter<File>> {
@@ -371,29 +714,137 @@ impl Writer<BufWriter<File>> {
         path: P,
         mut header: Header,
     ) -> Result<Writer<BufWriter<File>>> {
-        let compress = if cfg!(feature = "laz") {
-            match path.as_ref().extension() {
-                Some(ext) => match &ext.to_str() {
-                    Some(ext_str) => {
-                        if &ext_str.to_lowercase() == "laz" {
-                            true
-                        } else {
-                            false
-                        }
-                    }
-                    None => false,
-                },
-                None => false,
-            }
-        } else {
-            false
-        };
-
-        header.point_format_mut().is_compressed = compress;
+        header.point_format_mut().is_compressed = is_laz_path(path.as_ref());
         File::create(path)
             .map_err(::Error::from)
             .and_then(|file| Writer::new(BufWriter::new(file), header))
     }
+
+    /// Creates a new writer for a path, buffering writes with a buffer of `buffer_size` bytes.
+    ///
+    /// `from_path` uses the standard library's default buffer size, which can be small for large
+    /// files. Otherwise behaves like `from_path`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::Writer;
+    /// let writer = Writer::from_path_with_buffer_size("/dev/null", Default::default(), 1 << 20);
+    /// ```
+    pub fn from_path_with_buffer_size<P: AsRef<Path>>(
+        path: P,
+        mut header: Header,
+        buffer_size: usize,
+    ) -> Result<Writer<BufWriter<File>>> {
+        header.point_format_mut().is_compressed = is_laz_path(path.as_ref());
+        File::create(path)
+            .map_err(::Error::from)
+            .and_then(|file| Writer::new(BufWriter::with_capacity(buffer_size, file), header))
+    }
+
+    /// Writes points to a path atomically, so the path holds either its old contents or all of the
+    /// new data.
+    ///
+    /// The points are written to a sibling file with a `.tmp` suffix, which is synced to disk and
+    /// then renamed over `path`. If anything fails, the temporary file is removed and `path` is
+    /// untouched. Unlike `from_path`, the header's compression setting is used as-is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Header, Point, Writer};
+    /// let path = std::env::temp_dir().join("las-rs-to-path-atomic-doctest.las");
+    /// let points = vec![Point::default()];
+    /// Writer::to_path_atomic(&path, Header::default(), &points, Default::default()).unwrap();
+    /// # std::fs::remove_file(path).unwrap();
+    /// ```
+    pub fn to_path_atomic<P: AsRef<Path>>(
+        path: P,
+        header: Header,
+        points: &[Point],
+        options: WriteOptions,
+    ) -> Result<()> {
+        let path = path.as_ref();
+        let mut temp_path = path.as_os_str().to_owned();
+        temp_path.push(".tmp");
+        let temp_path = PathBuf::from(temp_path);
+        let result = File::create(&temp_path)
+            .map_err(::Error::from)
+            .and_then(|file| {
+                let write =
+                    Writer::write_with_options(BufWriter::new(file), header, points, options)?;
+                let file = write.into_inner().map_err(|err| err.into_error())?;
+                file.sync_all()?;
+                fs::rename(&temp_path, path)?;
+                Ok(())
+            });
+        if result.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+        result
+    }
+
+    /// Rewrites the header and vlrs of the las file at `path`, leaving its points in place if
+    /// possible.
+    ///
+    /// `header` should be the file's header, e.g. from a `Reader`, with changed vlrs or metadata.
+    /// If the new header and vlrs fit before the file's existing point data and the point format
+    /// and evlrs are unchanged, only that region is rewritten, padded with zeros up to the old
+    /// offset to point data. Otherwise the file's points are read and the whole file is rewritten
+    /// with `to_path_atomic`. Returns whether the file was rewritten in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use las::{Builder, Point, Read, Reader, Vlr, Writer};
+    /// let path = std::env::temp_dir().join("las-rs-rewrite-header-and-vlrs-doctest.las");
+    /// let mut builder = Builder::default();
+    /// builder.vlrs.push(Vlr { data: vec![0; 100], ..Default::default() });
+    /// let header = builder.into_header().unwrap();
+    /// Writer::to_path_atomic(&path, header, &[Point::default()], Default::default()).unwrap();
+    ///
+    /// let mut builder = Builder::from(Reader::from_path(&path).unwrap().header().clone());
+    /// builder.vlrs[0].data.truncate(10);
+    /// let header = builder.into_header().unwrap();
+    /// assert!(Writer::rewrite_header_and_vlrs(&path, header).unwrap());
+    /// # std::fs::remove_file(path).unwrap();
+    /// ```
+    pub fn rewrite_header_and_vlrs<P: AsRef<Path>>(path: P, header: Header) -> Result<bool> {
+        let path = path.as_ref();
+        let old = ::raw::Header::read_from(File::open(path)?)?;
+        let old_evlrs = Header::from_path(path)?.evlrs().clone();
+        let new = header.clone().into_raw()?;
+        if new.offset_to_point_data <= old.offset_to_point_data
+            && new.point_data_record_format == old.point_data_record_format
+            && new.point_data_record_length == old.point_data_record_length
+        {
+            let padded = pad_point_data(
+                header.clone(),
+                old.offset_to_point_data - new.offset_to_point_data,
+            )?;
+            if padded.clone().into_raw()?.evlr == old.evlr && *padded.evlrs() == old_evlrs {
+                let mut file = BufWriter::new(OpenOptions::new().write(true).open(path)?);
+                write_header_and_vlrs_to(&mut file, &padded)?;
+                std::io::Write::flush(&mut file)?;
+                return Ok(true);
+            }
+        }
+        let points = Reader::from_path(path)?
+            .points()
+            .collect::<Result<Vec<_>>>()?;
+        Writer::to_path_atomic(path, header, &points, Default::default())?;
+        Ok(false)
+    }
+}
+
+fn is_laz_path(path: &Path) -> bool {
+    if !cfg!(feature = "laz") {
+        return false;
+    }
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) => ext.eq_ignore_ascii_case("laz"),
+        None => false,
+    }
 }
 
 impl Default for Writer<Cursor<Vec<u8>>> {
@@ -416,7 +867,6 @@ mod tests {
 
     use header::Builder;
     use point::Format;
-    use Version;
 
     use super::*;
 
@@ -427,6 +877,239 @@ mod tests {
         Writer::new(Cursor::new(Vec::new()), builder.into_header().unwrap()).unwrap()
     }
 
+    #[test]
+    fn write_with_options_recompute_bounds() {
+        use {Bounds, Reader, Vector};
+
+        let points = vec![
+            Point {
+                x: 1.,
+                y: 2.,
+                z: 3.,
+                ..Default::default()
+            },
+            Point {
+                x: 4.,
+                y: 5.,
+                z: 6.,
+                ..Default::default()
+            },
+        ];
+        let mut header = Header::default();
+        for point in &[
+            Point::default(),
+            Point {
+                x: 10.,
+                y: 10.,
+                z: 10.,
+                ..Default::default()
+            },
+        ] {
+            header.add_point(point);
+        }
+        let tile = header.bounds();
+        let write = |options: WriteOptions| {
+            let cursor = Writer::write_with_options(
+                Cursor::new(Vec::new()),
+                header.clone(),
+                &points,
+                options,
+            )
+            .unwrap();
+            Reader::new(cursor).unwrap().header().bounds()
+        };
+        assert_eq!(
+            Bounds {
+                min: Vector {
+                    x: 1.,
+                    y: 2.,
+                    z: 3.
+                },
+                max: Vector {
+                    x: 4.,
+                    y: 5.,
+                    z: 6.
+                },
+            },
+            write(Default::default())
+        );
+        assert_eq!(
+            tile,
+            write(WriteOptions {
+                recompute_bounds: false,
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn write_with_options_optimize_scale() {
+        use {Read, Reader};
+
+        let points: Vec<_> = (0..10)
+            .map(|i| Point {
+                x: 5000. + f64::from(i) * 100.,
+                y: f64::from(i) * 0.1,
+                z: -1.,
+                ..Default::default()
+            })
+            .collect();
+        let options = WriteOptions {
+            auto_offsets: true,
+            optimize_scale: true,
+            ..Default::default()
+        };
+        let cursor = Writer::write_with_options(
+            Cursor::new(Vec::new()),
+            Header::default(),
+            &points,
+            options,
+        )
+        .unwrap();
+        let mut reader = Reader::new(cursor).unwrap();
+        let transforms = *reader.header().transforms();
+        assert_eq!(5000., transforms.x.offset);
+        assert_eq!(1e-6, transforms.x.scale);
+        assert_eq!(1e-9, transforms.y.scale);
+        assert_eq!(0.001, transforms.z.scale);
+        let read: Vec<_> = reader.points().map(|point| point.unwrap()).collect();
+        for (point, read) in points.iter().zip(&read) {
+            assert!((point.x - read.x).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn invalid_scale() {
+        let mut builder = Builder::default();
+        builder.transforms.x.scale = 0.;
+        assert!(Writer::new(Cursor::new(Vec::new()), builder.into_header().unwrap()).is_err());
+    }
+
+    #[test]
+    fn write_with_options_version() {
+        use {Read, Reader};
+
+        let header = Header::from((1, 4));
+        let points = vec![Point::default(); 3];
+        for &minor in &[2, 0] {
+            let options = WriteOptions {
+                version: Some(Version::new(1, minor)),
+                ..Default::default()
+            };
+            let cursor = Writer::write_with_options(
+                Cursor::new(Vec::new()),
+                header.clone(),
+                &points,
+                options,
+            )
+            .unwrap();
+            let mut reader = Reader::new(cursor).unwrap();
+            assert_eq!(Version::new(1, minor), reader.header().version());
+            assert_eq!(3, reader.points().count());
+        }
+        assert_eq!(Version::new(1, 4), header.version());
+    }
+
+    #[test]
+    fn write_with_options_point_data_alignment() {
+        use {raw, Read, Reader, Vlr};
+
+        let mut builder = Builder::default();
+        builder.vlrs.push(Vlr {
+            data: vec![1; 3],
+            ..Default::default()
+        });
+        let header = builder.into_header().unwrap();
+        for &minor in &[0, 2] {
+            let options = WriteOptions {
+                version: Some(Version::new(1, minor)),
+                point_data_alignment: Some(16),
+                ..Default::default()
+            };
+            let mut cursor = Writer::write_with_options(
+                Cursor::new(Vec::new()),
+                header.clone(),
+                &[Point::default()],
+                options,
+            )
+            .unwrap();
+            let raw_header = raw::Header::read_from(&mut cursor).unwrap();
+            assert_eq!(0, raw_header.offset_to_point_data % 16);
+            cursor.set_position(0);
+            let mut reader = Reader::new(cursor).unwrap();
+            assert_eq!(1, reader.points().count());
+            let header = reader.header().clone();
+            assert_eq!(header, options.apply(&header, &[]).unwrap());
+        }
+    }
+
+    #[test]
+    fn stamp_empty_metadata() {
+        use {Read, Reader};
+
+        let mut builder = Builder::default();
+        builder.generating_software = String::new();
+        builder.date = None;
+        let writer = Writer::new(Cursor::new(Vec::new()), builder.into_header().unwrap()).unwrap();
+        let reader = Reader::new(writer.into_inner().unwrap()).unwrap();
+        assert_eq!(
+            format!("las-rs {}", env!("CARGO_PKG_VERSION")),
+            reader.header().generating_software()
+        );
+        assert!(reader.header().date().is_some());
+    }
+
+    #[test]
+    fn stamp_keeps_metadata() {
+        use chrono::{TimeZone, Utc};
+        use {Read, Reader};
+
+        let mut builder = Builder::default();
+        builder.generating_software = "my tool".to_string();
+        builder.date = Some(Utc.ymd(2017, 10, 5));
+        let writer = Writer::new(Cursor::new(Vec::new()), builder.into_header().unwrap()).unwrap();
+        let reader = Reader::new(writer.into_inner().unwrap()).unwrap();
+        assert_eq!("my tool", reader.header().generating_software());
+        assert_eq!(Some(Utc.ymd(2017, 10, 5)), reader.header().date());
+    }
+
+    #[test]
+    fn write_with_options_skip_synthetic_return_counts() {
+        use {Read, Reader};
+
+        let points = vec![
+            Point {
+                return_number: 1,
+                number_of_returns: 1,
+                ..Default::default()
+            };
+            3
+        ];
+        let mut builder = Builder::from((1, 4));
+        builder.has_synthetic_return_numbers = true;
+        let header = builder.into_header().unwrap();
+        let write = |options: WriteOptions| {
+            let cursor = Writer::write_with_options(
+                Cursor::new(Vec::new()),
+                header.clone(),
+                &points,
+                options,
+            )
+            .unwrap();
+            Reader::new(cursor).unwrap().header().clone()
+        };
+
+        let counted = write(Default::default());
+        assert_eq!(Some(3), counted.number_of_points_by_return(1));
+
+        let skipped = write(WriteOptions {
+            skip_synthetic_return_counts: true,
+            ..Default::default()
+        });
+        assert_eq!(3, skipped.number_of_points());
+        assert_eq!(None, skipped.number_of_points_by_return(1));
+    }
+
     #[test]
     fn already_closed() {
         let mut writer = Writer::default();
@@ -445,6 +1128,25 @@ mod tests {
         assert!(writer.write(Default::default()).is_err());
     }
 
+    #[test]
+    fn unexpected_gps_time() {
+        let format = Format::new(0).unwrap();
+        let mut writer = writer(format, Version::new(1, 2));
+        writer.write(Default::default()).unwrap();
+        let point = Point {
+            gps_time: Some(42.),
+            ..Default::default()
+        };
+        match writer.write(point) {
+            Err(::Error::Writer(Error::PointFormat {
+                index: 1,
+                field: "gps_time",
+                ..
+            })) => {}
+            other => panic!("expected a point format error, got {:?}", other),
+        }
+    }
+
     #[test]
     fn missing_gps_time() {
         let format = Format::new(1).unwrap();
@@ -478,6 +1180,306 @@ mod tests {
         assert!(writer.write(Default::default()).is_err());
     }
 
+    #[test]
+    fn encode_decode_roundtrip() {
+        use reader::decode_point;
+        use Color;
+
+        let mut builder = Builder::from((1, 4));
+        builder.point_format = Format::new(7).unwrap();
+        let header = builder.into_header().unwrap();
+        let point = Point {
+            x: 1.,
+            y: 2.,
+            z: 3.,
+            return_number: 9,
+            number_of_returns: 10,
+            gps_time: Some(42.),
+            color: Some(Color::new(1, 2, 3)),
+            ..Default::default()
+        };
+        let mut buf = vec![0xff];
+        encode_point(&point, &header, &mut buf).unwrap();
+        assert_eq!(point, decode_point(&buf[1..], &header).unwrap());
+
+        assert!(encode_point(&Point::default(), &header, &mut buf).is_err());
+        assert_eq!(1 + usize::from(header.point_format().len()), buf.len());
+    }
+
+    #[test]
+    fn to_path_atomic() {
+        use {Read, Reader};
+
+        let path = std::env::temp_dir().join("las-rs-to-path-atomic.las");
+        let points = vec![
+            Point {
+                x: 1.,
+                ..Default::default()
+            };
+            3
+        ];
+        Writer::to_path_atomic(&path, Header::default(), &points, Default::default()).unwrap();
+        let mut temp_path = path.as_os_str().to_owned();
+        temp_path.push(".tmp");
+        assert!(!PathBuf::from(temp_path).exists());
+        let mut reader = Reader::from_path(&path).unwrap();
+        let read = reader.points().collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(points, read);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn from_path_with_buffer_size() {
+        use {Read, Reader};
+
+        let points = vec![
+            Point {
+                x: 1.,
+                ..Default::default()
+            };
+            100
+        ];
+        let write = |path: &Path, buffer_size: Option<usize>| {
+            let mut writer = match buffer_size {
+                Some(buffer_size) => {
+                    Writer::from_path_with_buffer_size(path, Header::default(), buffer_size)
+                }
+                None => Writer::from_path(path, Header::default()),
+            }
+            .unwrap();
+            for point in &points {
+                writer.write(point.clone()).unwrap();
+            }
+            writer.into_inner().unwrap()
+        };
+        let default_path = std::env::temp_dir().join("las-rs-buffer-size-default.las");
+        let large_path = std::env::temp_dir().join("las-rs-buffer-size-large.las");
+        write(&default_path, None);
+        assert_eq!(1 << 20, write(&large_path, Some(1 << 20)).capacity());
+        assert_eq!(
+            fs::read(&default_path).unwrap(),
+            fs::read(&large_path).unwrap()
+        );
+        let mut reader = Reader::from_path_with_buffer_size(&large_path, 1 << 20).unwrap();
+        let read = reader.points().collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(points, read);
+        fs::remove_file(default_path).unwrap();
+        fs::remove_file(large_path).unwrap();
+    }
+
+    #[test]
+    fn to_path_atomic_keeps_old_file_on_error() {
+        let path = std::env::temp_dir().join("las-rs-to-path-atomic-error.las");
+        fs::write(&path, b"old contents").unwrap();
+        let mut builder = Builder::default();
+        builder.transforms.x.scale = 0.;
+        let header = builder.into_header().unwrap();
+        assert!(Writer::to_path_atomic(&path, header, &[], Default::default()).is_err());
+        assert_eq!(b"old contents".to_vec(), fs::read(&path).unwrap());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn rewrite_header_and_vlrs_in_place() {
+        use Vlr;
+
+        let path = std::env::temp_dir().join("las-rs-rewrite-header-and-vlrs-in-place.las");
+        let mut builder = Builder::from((1, 2));
+        builder.vlrs.push(Vlr {
+            data: vec![42; 100],
+            ..Default::default()
+        });
+        let points: Vec<_> = (0..10)
+            .map(|i| Point {
+                x: f64::from(i),
+                ..Default::default()
+            })
+            .collect();
+        Writer::to_path_atomic(
+            &path,
+            builder.into_header().unwrap(),
+            &points,
+            Default::default(),
+        )
+        .unwrap();
+        let before = fs::read(&path).unwrap();
+        let offset = Reader::from_path(&path)
+            .unwrap()
+            .header()
+            .clone()
+            .into_raw()
+            .unwrap()
+            .offset_to_point_data as usize;
+
+        let mut builder = Builder::from(Reader::from_path(&path).unwrap().header().clone());
+        builder.vlrs[0].data = vec![7; 10];
+        let header = builder.into_header().unwrap();
+        assert!(Writer::rewrite_header_and_vlrs(&path, header).unwrap());
+
+        let after = fs::read(&path).unwrap();
+        assert_eq!(before.len(), after.len());
+        assert_eq!(before[offset..], after[offset..]);
+        let mut reader = Reader::from_path(&path).unwrap();
+        assert_eq!(vec![7; 10], reader.header().vlrs()[0].data);
+        let read = reader.points().collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(points, read);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn rewrite_header_and_vlrs_changed_evlrs() {
+        use Vlr;
+
+        let path = std::env::temp_dir().join("las-rs-rewrite-header-and-vlrs-evlrs.las");
+        let mut builder = Builder::from((1, 4));
+        builder.vlrs.push(Vlr {
+            data: vec![42; 100],
+            ..Default::default()
+        });
+        builder.evlrs.push(Vlr {
+            user_id: "old".to_string(),
+            data: vec![1; 10],
+            ..Default::default()
+        });
+        let points = vec![Point::default(); 3];
+        Writer::to_path_atomic(
+            &path,
+            builder.into_header().unwrap(),
+            &points,
+            Default::default(),
+        )
+        .unwrap();
+
+        let mut builder = Builder::from(Reader::from_path(&path).unwrap().header().clone());
+        builder.vlrs[0].data.truncate(50);
+        let header = builder.into_header().unwrap();
+        assert!(Writer::rewrite_header_and_vlrs(&path, header).unwrap());
+        let reader = Reader::from_path(&path).unwrap();
+        assert_eq!(vec![42; 50], reader.header().vlrs()[0].data);
+        assert_eq!(vec![1; 10], reader.header().evlrs()[0].data);
+
+        let mut builder = Builder::from(reader.header().clone());
+        builder.vlrs[0].data.truncate(10);
+        builder.evlrs[0] = Vlr {
+            user_id: "new".to_string(),
+            data: vec![2; 20],
+            ..Default::default()
+        };
+        let header = builder.into_header().unwrap();
+        assert!(!Writer::rewrite_header_and_vlrs(&path, header).unwrap());
+        let mut reader = Reader::from_path(&path).unwrap();
+        assert_eq!(vec![42; 10], reader.header().vlrs()[0].data);
+        assert_eq!(1, reader.header().evlrs().len());
+        assert_eq!("new", reader.header().evlrs()[0].user_id);
+        assert_eq!(vec![2; 20], reader.header().evlrs()[0].data);
+        let read = reader.points().collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(points, read);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn rewrite_header_and_vlrs_grown() {
+        use Vlr;
+
+        let path = std::env::temp_dir().join("las-rs-rewrite-header-and-vlrs-grown.las");
+        let points = vec![Point::default(); 3];
+        Writer::to_path_atomic(&path, Header::default(), &points, Default::default()).unwrap();
+        let mut builder = Builder::from(Reader::from_path(&path).unwrap().header().clone());
+        builder.vlrs.push(Vlr {
+            data: vec![42; 100],
+            ..Default::default()
+        });
+        let header = builder.into_header().unwrap();
+        assert!(!Writer::rewrite_header_and_vlrs(&path, header).unwrap());
+        let mut reader = Reader::from_path(&path).unwrap();
+        assert_eq!(vec![42; 100], reader.header().vlrs()[0].data);
+        let read = reader.points().collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(points, read);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn write_preserving_header_keeps_bounds() {
+        use {Read, Reader};
+
+        let mut builder = Builder::from((1, 2));
+        builder.transforms.x.offset = 100.;
+        let mut header = builder.into_header().unwrap();
+        header.add_point(&Point {
+            x: -10.,
+            y: -20.,
+            z: -30.,
+            ..Default::default()
+        });
+        header.add_point(&Point {
+            x: 10.,
+            y: 20.,
+            z: 30.,
+            ..Default::default()
+        });
+        let points = vec![Point::default()];
+        let cursor =
+            Writer::write_preserving_header(Cursor::new(Vec::new()), header.clone(), &points)
+                .unwrap();
+        let reader = Reader::new(cursor).unwrap();
+        assert_eq!(header.bounds(), reader.header().bounds());
+        assert_eq!(header.transforms(), reader.header().transforms());
+        assert_eq!(1, reader.header().number_of_points());
+    }
+
+    #[test]
+    fn write_raw_copies_records() {
+        use raw;
+        use {Read, Reader};
+
+        let source = fs::read("tests/data/autzen.las").unwrap();
+        let mut reader = Reader::new(Cursor::new(source.clone())).unwrap();
+        let mut writer = Writer::new(Cursor::new(Vec::new()), reader.header().clone()).unwrap();
+        while let Some(bytes) = reader.next_raw().unwrap() {
+            writer.write_raw(bytes).unwrap();
+        }
+        assert_eq!(reader.header().bounds(), writer.header().bounds());
+        let copy = writer.into_inner().unwrap().into_inner();
+
+        let point_block = |bytes: &[u8]| {
+            let header = raw::Header::read_from(bytes).unwrap();
+            let start = header.offset_to_point_data as usize;
+            let len = header.number_of_point_records as usize
+                * usize::from(header.point_data_record_length);
+            bytes[start..start + len].to_vec()
+        };
+        assert_eq!(point_block(&source), point_block(&copy));
+    }
+
+    #[test]
+    fn write_raw_wrong_length() {
+        let mut writer = Writer::default();
+        assert!(writer.write_raw(&[0; 3]).is_err());
+    }
+
+    #[test]
+    fn return_number_overflow() {
+        use {Read, Reader};
+
+        let mut writer = writer(Format::new(0).unwrap(), Version::new(1, 2));
+        let point = Point {
+            return_number: 8,
+            number_of_returns: 8,
+            ..Default::default()
+        };
+        assert!(writer.write(point).is_err());
+        let point = Point {
+            return_number: 5,
+            number_of_returns: 5,
+            ..Default::default()
+        };
+        writer.write(point.clone()).unwrap();
+        let mut reader = Reader::new(writer.into_inner().unwrap()).unwrap();
+        assert_eq!(1, reader.header().number_of_points());
+        assert_eq!(point, reader.read().unwrap().unwrap());
+        assert!(reader.read().is_none());
+    }
+
     #[test]
     fn write_not_at_start() {
         use byteorder::WriteBytesExt;
@@ -90,3 +90,308 @@ fn test_seek_0_works_on_las() {
 fn test_seek_0_works_on_laz() {
     test_seek_0_works_on("tests/data/autzen.laz");
 }
+
+#[test]
+fn points_by_return_matches_header() {
+    use las::{Read, Reader};
+    let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    let points = reader.points().collect::<Result<Vec<_>, _>>().unwrap();
+    let header = reader.header();
+    let points_by_return = header.points_by_return(&points);
+    for (i, &n) in points_by_return.iter().enumerate() {
+        let declared = header.number_of_points_by_return(i as u8 + 1).unwrap_or(0);
+        assert_eq!(declared, n);
+    }
+    assert_eq!(points.len() as u64, points_by_return.iter().sum());
+}
+
+#[test]
+fn point_data_hash_roundtrip() {
+    use las::{Read, Reader, Write, Writer};
+    use std::io::Cursor;
+
+    let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    let mut points = reader.points().collect::<Result<Vec<_>, _>>().unwrap();
+    let hash = reader.header().point_data_hash(&points).unwrap();
+
+    let mut writer = Writer::new(Cursor::new(Vec::new()), reader.header().clone()).unwrap();
+    for point in &points {
+        writer.write(point.clone()).unwrap();
+    }
+    let mut reader = Reader::new(writer.into_inner().unwrap()).unwrap();
+    let roundtrip_points = reader.points().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(
+        hash,
+        reader.header().point_data_hash(&roundtrip_points).unwrap()
+    );
+
+    points[42].x += 1.;
+    assert_ne!(hash, reader.header().point_data_hash(&points).unwrap());
+}
+
+#[test]
+fn find_projection_vlr() {
+    use las::{Read, Reader};
+
+    let reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    let vlr = reader
+        .header()
+        .find_vlr("LASF_Projection", 34735)
+        .expect("autzen has a geokey directory");
+    assert_eq!("LASF_Projection", vlr.user_id);
+    assert_eq!(34735, vlr.record_id);
+    assert!(reader.header().find_vlr("LASF_Projection", 1).is_none());
+}
+
+#[test]
+fn diff_pinpoints_changed_point() {
+    use las::{Diff, Read, Reader};
+
+    let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    let points = reader.points().collect::<Result<Vec<_>, _>>().unwrap();
+    let mut modified = points.clone();
+    modified[17].z += 1.;
+    let header = reader.header();
+    let diff = Diff::new(header, &points, header, &modified);
+    assert!(diff.header_fields.is_empty());
+    assert_eq!(None, diff.point_counts);
+    assert_eq!(Some(17), diff.first_point);
+    assert_eq!(1, diff.points);
+    assert_eq!(1, diff.coordinates);
+}
+
+#[test]
+fn header_from_path_matches_reader() {
+    use las::{Header, Read, Reader};
+
+    for path in &["tests/data/autzen.las", "tests/data/autzen.laz"] {
+        let header = Header::from_path(path).unwrap();
+        if let Ok(reader) = Reader::from_path(path) {
+            assert_eq!(reader.header(), &header);
+        }
+        assert_eq!(106, header.number_of_points());
+    }
+}
+
+#[test]
+fn reader_vlrs_match_header() {
+    use las::{Header, Reader};
+
+    let reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    let header = Header::from_path("tests/data/autzen.las").unwrap();
+    assert_eq!(header.vlrs().as_slice(), reader.vlrs());
+    assert_eq!(0, reader.points_read());
+}
+
+#[test]
+fn filter_by_gps_time() {
+    use las::{Header, Read, Reader};
+
+    let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    let points = reader.points().collect::<Result<Vec<_>, _>>().unwrap();
+    let mut times: Vec<_> = points.iter().map(|p| p.gps_time.unwrap()).collect();
+    times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let (start, end) = (times[20], times[80]);
+    let filtered = reader
+        .header()
+        .filter_by_gps_time(&points, start, end)
+        .unwrap();
+    assert!(!filtered.is_empty());
+    assert!(filtered.len() < points.len());
+    assert!(filtered
+        .iter()
+        .all(|p| p.gps_time.unwrap() >= start && p.gps_time.unwrap() <= end));
+    assert!(Header::default()
+        .filter_by_gps_time(&points, start, end)
+        .is_err());
+}
+
+#[test]
+fn clone_is_independent() {
+    use las::{Read, Reader};
+
+    let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    let header = reader.header().clone();
+    let points = reader.points().collect::<Result<Vec<_>, _>>().unwrap();
+
+    let mut cloned_header = header.clone();
+    let mut cloned_points = points.clone();
+    cloned_header.clear();
+    for point in &mut cloned_points {
+        point.x += 1.;
+        point.extra_bytes.push(42);
+    }
+
+    assert_eq!(reader.header(), &header);
+    let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    for (point, original) in points.iter().zip(reader.points()) {
+        assert_eq!(&original.unwrap(), point);
+    }
+}
+
+#[test]
+fn extended_format_roundtrip_zeroes_legacy_counts() {
+    use las::point::Format;
+    use las::{raw, Builder, Read, Reader, Write, Writer};
+    use std::io::{Cursor, Seek, SeekFrom};
+
+    let mut builder = Builder::from((1, 4));
+    builder.point_format = Format::new(6).unwrap();
+    let mut writer = Writer::new(Cursor::new(Vec::new()), builder.into_header().unwrap()).unwrap();
+    for i in 0..3 {
+        writer
+            .write(las::Point {
+                x: f64::from(i),
+                return_number: 1,
+                number_of_returns: 1,
+                gps_time: Some(f64::from(i)),
+                ..Default::default()
+            })
+            .unwrap();
+    }
+    let mut cursor = writer.into_inner().unwrap();
+
+    let raw_header = raw::Header::read_from(&mut cursor).unwrap();
+    assert_eq!(0, raw_header.number_of_point_records);
+    assert_eq!(3, raw_header.large_file.unwrap().number_of_point_records);
+
+    cursor.seek(SeekFrom::Start(0)).unwrap();
+    let mut reader = Reader::new(cursor).unwrap();
+    assert_eq!(3, reader.header().number_of_points());
+    let points = reader.points().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(3, points.len());
+    assert_eq!(2., points[2].x);
+}
+
+#[test]
+fn iter_classification_matches_histogram() {
+    use las::point::Classification;
+    use las::{Point, Read, Reader};
+    use std::collections::HashMap;
+
+    let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    let points = reader.points().collect::<Result<Vec<_>, _>>().unwrap();
+    let mut histogram = HashMap::new();
+    for point in &points {
+        *histogram.entry(u8::from(point.classification)).or_insert(0) += 1;
+    }
+    let ground = Point::iter_classification(&points, Classification::Ground).count();
+    assert_eq!(histogram.get(&2).cloned().unwrap_or(0), ground);
+    assert!(Point::iter_classification(&points, Classification::Ground)
+        .all(|point| point.classification == Classification::Ground));
+}
+
+#[test]
+fn compute_bounds_matches_header() {
+    use las::{Bounds, Read, Reader};
+
+    let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    let points = reader.points().collect::<Result<Vec<_>, _>>().unwrap();
+    let header = reader.header();
+    let bounds = Bounds::compute(&points);
+    let expected = header.bounds();
+    let transforms = header.transforms();
+    for &(actual, expected, scale) in &[
+        (bounds.min.x, expected.min.x, transforms.x.scale),
+        (bounds.min.y, expected.min.y, transforms.y.scale),
+        (bounds.min.z, expected.min.z, transforms.z.scale),
+        (bounds.max.x, expected.max.x, transforms.x.scale),
+        (bounds.max.y, expected.max.y, transforms.y.scale),
+        (bounds.max.z, expected.max.z, transforms.z.scale),
+    ] {
+        assert!((actual - expected).abs() <= scale);
+    }
+}
+
+#[test]
+fn scan_directions() {
+    use las::point::ScanDirection;
+    use las::{Read, Reader};
+
+    let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    for point in reader.points() {
+        let point = point.unwrap();
+        let expected = match point.scan_direction {
+            ScanDirection::RightToLeft => "right to left",
+            ScanDirection::LeftToRight => "left to right",
+        };
+        assert_eq!(expected, point.scan_direction.to_string());
+    }
+}
+
+#[test]
+fn write_preserving_header_reproduces_file() {
+    use las::{Read, Reader, Writer};
+    use std::io::Cursor;
+
+    let original = std::fs::read("tests/data/autzen.las").unwrap();
+    let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    let header = reader.header().clone();
+    let points = reader.points().collect::<Result<Vec<_>, _>>().unwrap();
+    let cursor = Writer::write_preserving_header(Cursor::new(Vec::new()), header, &points).unwrap();
+    assert_eq!(original, cursor.into_inner());
+}
+
+#[test]
+fn clear_vlrs_shrinks_offset_to_point_data() {
+    use las::{raw, Read, Reader, Writer};
+    use std::io::Cursor;
+
+    let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    let mut header = reader.header().clone();
+    let points = reader.points().collect::<Result<Vec<_>, _>>().unwrap();
+    let offset_to_point_data = header.clone().into_raw().unwrap().offset_to_point_data;
+    header.clear_vlrs(None);
+    let mut cursor =
+        Writer::write_preserving_header(Cursor::new(Vec::new()), header, &points).unwrap();
+    let raw_header = raw::Header::read_from(&mut cursor).unwrap();
+    assert_eq!(0, raw_header.number_of_variable_length_records);
+    assert!(raw_header.offset_to_point_data < offset_to_point_data);
+    cursor.set_position(0);
+    let mut reader = Reader::new(cursor).unwrap();
+    assert!(reader.vlrs().is_empty());
+    assert_eq!(points.len(), reader.points().count());
+}
+
+#[test]
+fn point_density_of_a_grid() {
+    use las::{Header, Point};
+
+    let mut points = Vec::new();
+    for i in 0..10 {
+        for j in 0..10 {
+            points.push(Point {
+                x: f64::from(i) * 10. / 9.,
+                y: f64::from(j) * 10. / 9.,
+                ..Default::default()
+            });
+        }
+    }
+    let header = Header::from_points(&points);
+    assert!((header.point_density() - 1.).abs() < 1e-9);
+    assert!((Point::density_from_hull(&points) - 1.).abs() < 1e-9);
+}
+
+#[test]
+fn read_order_matches_sequential_records() {
+    use las::reader::decode_point;
+    use las::{raw, Point, Read, Reader};
+
+    let bytes = std::fs::read("tests/data/autzen.las").unwrap();
+    let mut reader = Reader::from_path("tests/data/autzen.las").unwrap();
+    let points = reader.points().collect::<Result<Vec<_>, _>>().unwrap();
+
+    let raw_header = raw::Header::read_from(&bytes[..]).unwrap();
+    let start = raw_header.offset_to_point_data as usize;
+    let len = usize::from(raw_header.point_data_record_length);
+    let records = bytes[start..start + points.len() * len]
+        .chunks(len)
+        .map(|record| decode_point(record, reader.header()).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(records, points);
+
+    let sorted = points
+        .windows(2)
+        .all(|pair| pair[0].gps_time <= pair[1].gps_time);
+    assert_eq!(sorted, Point::is_sorted_by_gps_time(&points));
+}
@@ -98,6 +98,12 @@ macro_rules! version {
                 roundtrip_point!(is_synthetic, |p: &mut Point| p.is_synthetic = true);
                 roundtrip_point!(is_key_point, |p: &mut Point| p.is_key_point = true);
                 roundtrip_point!(is_withheld, |p: &mut Point| p.is_withheld = true);
+                roundtrip_point!(classification_flags, |p: &mut Point| {
+                    p.classification = Classification::Reserved(28);
+                    p.is_synthetic = true;
+                    p.is_key_point = true;
+                    p.is_withheld = true;
+                });
                 roundtrip_point!(is_overlap, |p: &mut Point| {
                     p.classification = Classification::Unclassified;
                     p.is_overlap = true;
@@ -207,14 +213,13 @@ macro_rules! version {
                     b"You probably shouldn't do this".to_vec());
                 roundtrip_builder!(vlr_padding, |b: &mut Builder| b.vlr_padding =
                     b"You probably shouldn't do this either".to_vec());
-                roundtrip_builder!(
-                    point_padding,
-                    |b: &mut Builder| {
-                        b.point_padding = vec![42];
-                        b.evlrs.push(Vlr::default());
-                    },
-                    4
-                );
+                roundtrip_builder!(point_padding, |b: &mut Builder| {
+                    b.point_padding = vec![42];
+                    b.evlrs.push(Vlr::default());
+                });
+                roundtrip_builder!(point_padding_without_evlrs, |b: &mut Builder| {
+                    b.point_padding = vec![42];
+                });
             }
         }
     };